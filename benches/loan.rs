@@ -0,0 +1,107 @@
+use std::mem::MaybeUninit;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use loaned::{take, LoanedMut, VecPlace};
+
+fn loan_into(c: &mut Criterion) {
+  let mut group = c.benchmark_group("loan_into");
+
+  group.bench_function("fresh", |b| {
+    b.iter(|| {
+      let (r, l) = LoanedMut::loan(Box::new([0u64; 16]));
+      r[0] = black_box(1);
+      black_box(take!(l))
+    })
+  });
+
+  group.bench_function("reused", |b| {
+    let mut storage = Some(Box::new(MaybeUninit::uninit()));
+    b.iter(|| {
+      let (r, l) = LoanedMut::loan_into(storage.take().unwrap(), [0u64; 16]);
+      r[0] = black_box(1);
+      let value = take!(l);
+      black_box(&value);
+      storage = Some(unsafe { Box::from_raw(Box::into_raw(value).cast()) });
+    })
+  });
+
+  group.finish();
+}
+
+fn place(c: &mut Criterion) {
+  let mut group = c.benchmark_group("place");
+
+  group.bench_function("value", |b| {
+    b.iter(|| {
+      let mut x = Box::new(0u64);
+      let (r, l) = LoanedMut::loan(Box::new(1));
+      l.place(&mut x);
+      *r = black_box(2);
+      black_box(x)
+    })
+  });
+
+  group.bench_function("maybe_uninit", |b| {
+    b.iter(|| {
+      let mut x = MaybeUninit::uninit();
+      let (r, l) = LoanedMut::loan(Box::new(1u64));
+      l.place(&mut x);
+      *r = black_box(2);
+      black_box(unsafe { x.assume_init() })
+    })
+  });
+
+  group.bench_function("option", |b| {
+    b.iter(|| {
+      let mut x = None;
+      let (r, l) = LoanedMut::loan(Box::new(1u64));
+      l.place(&mut x);
+      *r = black_box(2);
+      black_box(x)
+    })
+  });
+
+  group.bench_function("vec", |b| {
+    b.iter_batched(
+      || Vec::with_capacity(1024),
+      |mut vec| {
+        let mut place = VecPlace::new(&mut vec);
+        for i in 0..1024u64 {
+          place.push(LoanedMut::new(Box::new(i)));
+        }
+        black_box(vec)
+      },
+      BatchSize::SmallInput,
+    )
+  });
+
+  group.finish();
+}
+
+#[derive(Default)]
+struct Node(Option<Box<Node>>, Option<Box<Node>>);
+
+fn build_tree<'t>(depth: u32) -> LoanedMut<'t, Box<Node>> {
+  if depth == 0 {
+    return LoanedMut::new(Box::new(Node::default()));
+  }
+  let left = build_tree(depth - 1);
+  let right = build_tree(depth - 1);
+  LoanedMut::merge(Box::default(), |node, m| {
+    m.place(left, &mut node.0);
+    m.place(right, &mut node.1);
+  })
+}
+
+fn tree(c: &mut Criterion) {
+  let mut group = c.benchmark_group("tree");
+
+  group.bench_function("merge_take", |b| {
+    b.iter(|| black_box(take!(build_tree(10))))
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, loan_into, place, tree);
+criterion_main!(benches);
@@ -1,58 +1,8 @@
-//! A typed arena allocator implementation with no unsafe code or interior
-//! mutability.
-//!
-//! API inspired by the [`typed_arena`] crate.
-//!
-//! [`typed_arena`]: https://docs.rs/typed-arena/latest/typed_arena/
+//! Building a cyclic structure with the typed arena in [`loaned::arena`].
 
-use std::{cell::Cell, mem};
+use std::cell::Cell;
 
-use loaned::{drop, LoanedMut};
-
-pub struct Arena<'t, T> {
-  cursor: &'t mut [Option<T>],
-  chunks: Vec<LoanedMut<'t, Box<[Option<T>]>>>,
-  capacity: usize,
-}
-
-impl<'t, T> Arena<'t, T> {
-  pub fn with_capacity(capacity: usize) -> Self {
-    let capacity = capacity.max(1);
-    let (cursor, chunk) = Self::new_chunk(capacity);
-    Arena {
-      cursor,
-      chunks: vec![chunk],
-      capacity,
-    }
-  }
-
-  fn new_chunk(capacity: usize) -> (&'t mut [Option<T>], LoanedMut<'t, Box<[Option<T>]>>) {
-    let mut chunk = Vec::with_capacity(capacity);
-    chunk.resize_with(capacity, || None);
-    let chunk = chunk.into_boxed_slice();
-    let (cursor, chunk) = LoanedMut::loan(chunk);
-    (cursor, chunk)
-  }
-
-  pub fn alloc(&mut self, value: T) -> &'t mut T {
-    if self.cursor.is_empty() {
-      self.capacity *= 2;
-      let (cursor, chunk) = Self::new_chunk(self.capacity);
-      self.cursor = cursor;
-      self.chunks.push(chunk);
-    }
-    let cursor = mem::replace(&mut self.cursor, &mut []);
-    let (slot, cursor) = cursor.split_first_mut().unwrap();
-    self.cursor = cursor;
-    *slot = Some(value);
-    let Some(slot) = slot else { unreachable!() };
-    slot
-  }
-
-  pub fn into_inner(self) -> LoanedMut<'t, Vec<Box<[Option<T>]>>> {
-    self.chunks.into()
-  }
-}
+use loaned::{arena::Arena, drop};
 
 #[cfg_attr(test, test)]
 fn main() {
@@ -0,0 +1,203 @@
+//! Derive macros for the [`loaned`](https://docs.rs/loaned) crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, Lifetime};
+
+/// Derives `LoanProject` for a struct, generating a `{Name}Fields<'t>` struct
+/// with a `LoanedMut<'t, _>` for each field.
+#[proc_macro_derive(LoanProject)]
+pub fn derive_loan_project(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  loan_project(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+/// Derives `place_{variant}` methods for each tuple variant of an enum, which
+/// overwrite the enum with that variant, constructed from loans of each of its
+/// fields. Unit variants are skipped, and named-field variants are rejected.
+#[proc_macro_derive(Place)]
+pub fn derive_place(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  place(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+fn loan_lifetime(generics: &syn::Generics) -> Lifetime {
+  if generics.lifetimes().any(|l| l.lifetime.ident == "t") {
+    Lifetime::new("'__t", Span::call_site())
+  } else {
+    Lifetime::new("'t", Span::call_site())
+  }
+}
+
+fn place(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let Data::Enum(data) = &input.data else {
+    return Err(Error::new_spanned(
+      &input.ident,
+      "`Place` can only be derived for enums",
+    ));
+  };
+
+  let name = &input.ident;
+  let t = loan_lifetime(&input.generics);
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  // Named variants have no constructor function to build them from
+  // `MaybeUninit`s with, and silently skipping them would be surprising.
+  if let Some(variant) = data
+    .variants
+    .iter()
+    .find(|variant| matches!(variant.fields, Fields::Named(_)))
+  {
+    return Err(Error::new_spanned(
+      &variant.fields,
+      "`Place` cannot be derived for enums with named-field variants",
+    ));
+  }
+
+  let methods = data.variants.iter().filter_map(|variant| {
+    let Fields::Unnamed(fields) = &variant.fields else {
+      return None;
+    };
+    let variant_name = &variant.ident;
+    let method = format_ident!("place_{}", snake_case(&variant_name.to_string()));
+    let tys = fields
+      .unnamed
+      .iter()
+      .map(|field| &field.ty)
+      .collect::<Vec<_>>();
+    let args = (0..tys.len())
+      .map(|i| format_ident!("field{}", i))
+      .collect::<Vec<_>>();
+    let holes = tys.iter().map(|_| quote!(_));
+    let doc =
+      format!("Overwrites `self` with [`{name}::{variant_name}`], constructed from loaned fields.");
+    Some(quote! {
+      #[doc = #doc]
+      #[allow(clippy::too_many_arguments)]
+      #[inline]
+      pub fn #method<#t>(&#t mut self, #(#args: ::loaned::LoanedMut<#t, #tys>),*) {
+        unsafe {
+          // Constructing the variant from `MaybeUninit`s avoids moving the
+          // fields as their types, which would invalidate the loans.
+          let ctor = ::core::mem::transmute::<
+            fn(#(#tys),*) -> Self,
+            fn(#(::core::mem::MaybeUninit<#tys>),*) -> ::core::mem::MaybeUninit<Self>,
+          >(Self::#variant_name as fn(#(#holes),*) -> _);
+          let value = ctor(#(::loaned::__into_maybe_uninit(#args)),*);
+          ::loaned::__place_variant(self, value);
+        }
+      }
+    })
+  });
+
+  Ok(quote! {
+    impl #impl_generics #name #ty_generics #where_clause {
+      #(#methods)*
+    }
+  })
+}
+
+fn snake_case(name: &str) -> String {
+  let mut snake = String::new();
+  for (i, c) in name.chars().enumerate() {
+    if c.is_uppercase() {
+      if i != 0 {
+        snake.push('_');
+      }
+      snake.extend(c.to_lowercase());
+    } else {
+      snake.push(c);
+    }
+  }
+  snake
+}
+
+fn loan_project(input: DeriveInput) -> syn::Result<TokenStream2> {
+  for attr in &input.attrs {
+    if attr.path().is_ident("repr") {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("packed") {
+          return Err(meta.error("`LoanProject` cannot be derived for packed structs"));
+        }
+        if meta.input.peek(syn::token::Paren) {
+          let content;
+          syn::parenthesized!(content in meta.input);
+          content.parse::<TokenStream2>()?;
+        }
+        Ok(())
+      })?;
+    }
+  }
+
+  let Data::Struct(data) = &input.data else {
+    return Err(Error::new_spanned(
+      &input.ident,
+      "`LoanProject` can only be derived for structs",
+    ));
+  };
+  if data.fields.is_empty() {
+    return Err(Error::new_spanned(
+      &input.ident,
+      "`LoanProject` cannot be derived for structs without fields",
+    ));
+  }
+
+  let name = &input.ident;
+  let vis = &input.vis;
+  let fields_name = format_ident!("{}Fields", name);
+
+  let t = loan_lifetime(&input.generics);
+  let mut generics = input.generics.clone();
+  generics.params.insert(
+    0,
+    GenericParam::Lifetime(syn::LifetimeParam::new(t.clone())),
+  );
+  let (impl_generics, fields_ty_generics, where_clause) = generics.split_for_impl();
+  let (_, ty_generics, _) = input.generics.split_for_impl();
+
+  let members = data.fields.members().collect::<Vec<_>>();
+  let field_defs = data.fields.iter().map(|field| {
+    let vis = &field.vis;
+    let ty = &field.ty;
+    match &field.ident {
+      Some(ident) => quote!(#vis #ident: ::loaned::LoanedMut<#t, #ty>),
+      None => quote!(#vis ::loaned::LoanedMut<#t, #ty>),
+    }
+  });
+  let body = match &data.fields {
+    Fields::Named(_) => quote!(#where_clause { #(#field_defs,)* }),
+    _ => quote!(( #(#field_defs,)* ) #where_clause;),
+  };
+  let doc = format!("Loans of each of the fields of [`{name}`]; see `loaned::LoanProject`.");
+
+  Ok(quote! {
+    #[doc = #doc]
+    #[allow(missing_docs)]
+    #vis struct #fields_name #impl_generics #body
+
+    unsafe impl #impl_generics ::loaned::LoanProject<#t> for #name #ty_generics #where_clause {
+      type Fields = #fields_name #fields_ty_generics;
+
+      fn split(loaned: ::loaned::LoanedMut<#t, Self>) -> Self::Fields {
+        unsafe {
+          ::loaned::__split(loaned, |ptr: *mut Self| #fields_name {
+            #(#members: ::loaned::__read_field(::core::ptr::addr_of_mut!((*ptr).#members)),)*
+          })
+        }
+      }
+
+      fn merge(fields: Self::Fields) -> ::loaned::LoanedMut<#t, Self> {
+        unsafe {
+          ::loaned::__merge(|ptr: *mut Self| {
+            #(::loaned::__write_field(::core::ptr::addr_of_mut!((*ptr).#members), fields.#members);)*
+          })
+        }
+      }
+    }
+  })
+}
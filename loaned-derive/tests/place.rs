@@ -0,0 +1,25 @@
+use loaned::{take, LoanedMut, Place};
+
+#[derive(Place, Debug, Default, PartialEq)]
+enum Tree {
+  #[default]
+  Null,
+  Leaf(i32),
+  Node(Box<Tree>, Box<Tree>),
+}
+
+#[test]
+fn place_variant() {
+  let (root, tree) = LoanedMut::loan(Box::new(Tree::Null));
+  let (left, l) = LoanedMut::loan(Box::new(Tree::Null));
+  let (right, r) = LoanedMut::loan(Box::new(Tree::Null));
+  root.place_node(l, r);
+  left.place_leaf(LoanedMut::new(1));
+  let (a, x) = LoanedMut::loan(Box::new(Tree::Null));
+  let (b, y) = LoanedMut::loan(Box::new(Tree::Null));
+  right.place_node(x, y);
+  *a = Tree::Leaf(2);
+  *b = Tree::Leaf(3);
+  let tree = take!(tree);
+  assert_eq!(format!("{tree:?}"), "Node(Leaf(1), Node(Leaf(2), Leaf(3)))");
+}
@@ -0,0 +1,47 @@
+use loaned::{take, LoanProject, LoanedMut};
+
+#[derive(LoanProject, Debug, PartialEq)]
+struct Node<'a, T> {
+  name: &'a str,
+  left: Box<T>,
+  right: Box<T>,
+}
+
+#[derive(LoanProject, Debug, PartialEq)]
+#[repr(C, align(8))]
+struct Pair(Box<u32>, u32);
+
+#[test]
+fn split_merge() {
+  let (l, left) = LoanedMut::loan(Box::new(0));
+  let (r, right) = LoanedMut::loan(Box::new(0));
+  let node = Node::merge(NodeFields {
+    name: LoanedMut::new("a"),
+    left,
+    right,
+  });
+  *l = 1;
+  let NodeFields { name, left, right } = Node::split(node);
+  *r = 2;
+  let node = Node::merge(NodeFields {
+    name,
+    left: right,
+    right: left,
+  });
+  assert_eq!(
+    take!(node),
+    Node {
+      name: "a",
+      left: Box::new(2),
+      right: Box::new(1),
+    }
+  );
+}
+
+#[test]
+fn tuple_struct() {
+  let (r, a) = LoanedMut::loan(Box::new(0));
+  let PairFields(a, b) = Pair::split(Pair::merge(PairFields(a, LoanedMut::new(2))));
+  *r = 1;
+  assert_eq!(take!(Pair::merge(PairFields(a, b))), Pair(Box::new(1), 2));
+}
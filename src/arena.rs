@@ -0,0 +1,131 @@
+//! A typed arena allocator built on loans.
+//!
+//! API inspired by the [`typed_arena`] crate.
+//!
+//! [`typed_arena`]: https://docs.rs/typed-arena/latest/typed_arena/
+
+use crate::*;
+
+/// A typed arena, which hands out `&'t mut T` references to the values
+/// allocated in it.
+///
+/// Values are stored in chunks whose capacity doubles as the arena grows; they
+/// are never moved once allocated. Once `'t` has expired, the chunks can be
+/// taken from [`Arena::into_inner`].
+///
+/// # Example
+/// ```
+/// use loaned::{arena::Arena, drop};
+/// use std::cell::Cell;
+///
+/// struct Node<'a> {
+///   name: &'static str,
+///   next: Cell<Option<&'a Node<'a>>>,
+/// }
+///
+/// let mut arena = Arena::new();
+/// let a = arena.alloc(Node { name: "a", next: Cell::new(None) });
+/// let b = arena.alloc(Node { name: "b", next: Cell::new(None) });
+/// a.next.set(Some(b));
+/// b.next.set(Some(a));
+/// assert_eq!(a.next.get().unwrap().next.get().unwrap().name, "a");
+/// drop!(arena.into_inner());
+/// ```
+pub struct Arena<'t, T> {
+  chunk: LoanedMut<'t, Vec<T>>,
+  chunks: Vec<LoanedMut<'t, Vec<T>>>,
+  len: usize,
+}
+
+impl<'t, T> Arena<'t, T> {
+  /// Constructs a new, empty `Arena`.
+  #[inline]
+  pub fn new() -> Self {
+    Arena::with_capacity(8)
+  }
+
+  /// Constructs a new, empty `Arena`, whose first chunk can hold at least
+  /// `capacity` values.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Arena {
+      chunk: LoanedMut::new(Vec::with_capacity(capacity.max(1))),
+      chunks: Vec::new(),
+      len: 0,
+    }
+  }
+
+  /// Allocates a value in the arena, returning a mutable reference to it.
+  pub fn alloc(&mut self, value: T) -> &'t mut T {
+    let chunk = self.reserve(1);
+    unsafe {
+      let len = chunk.len();
+      let ptr = chunk.as_mut_ptr().add(len);
+      ptr.write(value);
+      chunk.set_len(len + 1);
+      self.len += 1;
+      &mut *ptr
+    }
+  }
+
+  /// Allocates the values of an iterator contiguously in the arena, returning
+  /// a mutable reference to them.
+  pub fn alloc_iter(&mut self, iter: impl IntoIterator<Item = T>) -> &'t mut [T] {
+    let mut values = iter.into_iter().collect::<Vec<_>>();
+    let count = values.len();
+    let chunk = self.reserve(count);
+    unsafe {
+      let len = chunk.len();
+      let ptr = chunk.as_mut_ptr().add(len);
+      ptr::copy_nonoverlapping(values.as_ptr(), ptr, count);
+      values.set_len(0);
+      chunk.set_len(len + count);
+      self.len += count;
+      core::slice::from_raw_parts_mut(ptr, count)
+    }
+  }
+
+  /// Returns the number of values allocated in the arena.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns `true` if no values have been allocated in the arena.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the chunks of the arena, which contain all of the values
+  /// allocated in it, in order.
+  pub fn into_inner(self) -> LoanedMut<'t, Vec<Vec<T>>> {
+    let Arena {
+      chunk, mut chunks, ..
+    } = self;
+    chunks.push(chunk);
+    chunks.into()
+  }
+
+  /// Returns the current chunk, starting a new one if it cannot fit
+  /// `additional` more values.
+  fn reserve(&mut self, additional: usize) -> &mut Vec<T> {
+    // The values in the chunks are loaned, but the `Vec`s themselves are not;
+    // they are only ever accessed through raw pointers.
+    let chunk = unsafe { self.chunk.inner.as_mut() };
+    if chunk.capacity() - chunk.len() < additional {
+      let capacity = chunk.capacity().saturating_mul(2).max(additional);
+      let chunk = mem::replace(
+        &mut self.chunk,
+        LoanedMut::new(Vec::with_capacity(capacity)),
+      );
+      self.chunks.push(chunk);
+    }
+    unsafe { self.chunk.inner.as_mut() }
+  }
+}
+
+impl<'t, T> Default for Arena<'t, T> {
+  fn default() -> Self {
+    Arena::new()
+  }
+}
@@ -0,0 +1,145 @@
+//! Branded regions, for tagging groups of loans so that loans from different
+//! groups can't be mixed up.
+//!
+//! Loan lifetimes are contravariant, so two unrelated `'t` lifetimes may be
+//! unified by the compiler, allowing a loan to be placed into an unrelated
+//! structure that happens to be typed with the same `'t`. A [`Region<'id>`]
+//! carries an invariant, unique `'id` brand (created with [`region`]); loans
+//! tagged with it as [`Branded<'id, _>`] can only be combined with loans from
+//! the same region.
+//!
+//! APIs can require loans from a particular region by taking a `Region<'id>`
+//! on construction and only accepting `Branded<'id, _>` values afterwards.
+//!
+//! # Example
+//! ```
+//! use loaned::{brand::{region, Branded, Region}, take, LoanedMut};
+//!
+//! struct Builder<'id, 't> {
+//!   nodes: Vec<LoanedMut<'t, Box<u32>>>,
+//!   _region: Region<'id>,
+//! }
+//!
+//! impl<'id, 't> Builder<'id, 't> {
+//!   fn add(&mut self, node: Branded<'id, LoanedMut<'t, Box<u32>>>) {
+//!     self.nodes.push(node.into_inner());
+//!   }
+//! }
+//!
+//! let nodes = region(|r| {
+//!   let mut builder = Builder { nodes: Vec::new(), _region: r };
+//!   let (a, loaned) = r.loan(Box::new(1));
+//!   builder.add(loaned);
+//!   *a += 1;
+//!   LoanedMut::<Vec<Box<_>>>::from(builder.nodes)
+//! });
+//! assert_eq!(take!(nodes), [Box::new(2)]);
+//! ```
+
+use crate::*;
+
+/// An invariant brand lifetime, unique to a call of [`region`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Region<'id>(PhantomData<fn(&'id ()) -> &'id ()>);
+
+/// Calls `f` with a new [`Region`], whose brand is distinct from that of any
+/// other region.
+#[inline]
+pub fn region<R>(f: impl for<'id> FnOnce(Region<'id>) -> R) -> R {
+  f(Region(PhantomData))
+}
+
+/// A value (typically a [`Loaned`] or [`LoanedMut`]) tagged with the brand of
+/// a [`Region`].
+#[derive(Debug)]
+pub struct Branded<'id, L> {
+  value: L,
+  _region: Region<'id>,
+}
+
+impl<'id> Region<'id> {
+  /// Tags a value with this region's brand.
+  #[inline]
+  pub fn brand<L>(self, value: L) -> Branded<'id, L> {
+    Branded {
+      value,
+      _region: self,
+    }
+  }
+
+  /// Mutably loans a value, like [`LoanedMut::loan`], tagging the loan with
+  /// this region's brand.
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan<'t, T>(self, value: T) -> (&'t mut T::Target, Branded<'id, LoanedMut<'t, T>>)
+  where
+    T: Loanable<'t> + DerefMut,
+  {
+    let (borrow, loaned) = LoanedMut::loan(value);
+    (borrow, self.brand(loaned))
+  }
+
+  /// Immutably loans a value, like [`Loaned::loan`], tagging the loan with
+  /// this region's brand.
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_shared<'t, T>(self, value: T) -> (&'t T::Target, Branded<'id, Loaned<'t, T>>)
+  where
+    T: Loanable<'t>,
+  {
+    let (borrow, loaned) = Loaned::loan(value);
+    (borrow, self.brand(loaned))
+  }
+}
+
+impl<'id, L> Branded<'id, L> {
+  /// Returns the region the value is branded with.
+  #[inline]
+  pub fn region(&self) -> Region<'id> {
+    self._region
+  }
+
+  /// Removes the brand from the value.
+  #[inline]
+  pub fn into_inner(self) -> L {
+    self.value
+  }
+
+  /// Applies `f` to the branded value, keeping the brand.
+  #[inline]
+  pub fn map<M>(self, f: impl FnOnce(L) -> M) -> Branded<'id, M> {
+    self._region.brand(f(self.value))
+  }
+
+  /// Combines two values branded with the same region.
+  #[inline]
+  pub fn zip<M>(self, other: Branded<'id, M>) -> Branded<'id, (L, M)> {
+    self._region.brand((self.value, other.value))
+  }
+}
+
+impl<'id, 't, L: LoanWrapper<'t>> LoanWrapper<'t> for Branded<'id, L> {
+  type Value = L::Value;
+  #[inline]
+  fn into_loan(self) -> LoanedMut<'t, L::Value> {
+    self.value.into_loan()
+  }
+}
+
+impl<'id, 't, L: SharedLoanWrapper<'t>> SharedLoanWrapper<'t> for Branded<'id, L> {
+  #[inline]
+  fn into_shared_loan(self) -> Loaned<'t, L::Value> {
+    self.value.into_shared_loan()
+  }
+}
+
+crate::compile_fail_test!(mix_regions: "E0521" {
+  use loaned::brand::region;
+  region(|a| {
+    region(|b| {
+      let x = a.brand(());
+      let y = b.brand(());
+      let _ = x.zip(y);
+    })
+  });
+});
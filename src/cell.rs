@@ -0,0 +1,140 @@
+use crate::*;
+use core::{
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll, Waker},
+};
+use std::sync::Mutex;
+
+/// A place that can be shared between threads or tasks, allowing one of them
+/// to place a [`LoanedMut`] into it while another holds the loaned borrow.
+///
+/// [`LoanedCell::placed`] returns a future which resolves once a value has been
+/// placed. Since placing borrows the cell for `'t`, the value can only be
+/// retrieved (with [`LoanedCell::into_inner`] or [`LoanedCell::take`]) once
+/// `'t` has expired.
+///
+/// # Example
+/// ```
+/// use loaned::{LoanedCell, LoanedMut};
+/// let cell = LoanedCell::new();
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(0));
+/// std::thread::scope(|s| {
+///   s.spawn(|| cell.place(loaned).unwrap());
+///   *borrow = 123;
+/// });
+/// assert_eq!(cell.into_inner(), Some(Box::new(123)));
+/// ```
+pub struct LoanedCell<T> {
+  state: Mutex<CellState<T>>,
+}
+
+struct CellState<T> {
+  value: Option<RawLoaned<T>>,
+  wakers: Vec<Waker>,
+}
+
+impl<T> LoanedCell<T> {
+  /// Constructs a new, empty `LoanedCell`.
+  #[inline]
+  pub const fn new() -> Self {
+    LoanedCell {
+      state: Mutex::new(CellState {
+        value: None,
+        wakers: Vec::new(),
+      }),
+    }
+  }
+
+  /// Places a loaned value into the cell, waking any tasks awaiting
+  /// [`LoanedCell::placed`]. If the cell already contains a value, the loaned
+  /// value is returned instead.
+  pub fn place<'t>(&'t self, loaned: LoanedMut<'t, T>) -> Result<(), LoanedMut<'t, T>> {
+    let mut state = self.lock();
+    if state.value.is_some() {
+      return Err(loaned);
+    }
+    state.value = Some(loaned.into_raw());
+    for waker in state.wakers.drain(..) {
+      waker.wake();
+    }
+    Ok(())
+  }
+
+  /// Returns `true` if a value has been placed into the cell.
+  pub fn is_placed(&self) -> bool {
+    self.lock().value.is_some()
+  }
+
+  /// Returns a future which resolves once a value has been placed into the
+  /// cell.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{LoanedCell, LoanedMut};
+  /// use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+  /// let cell = LoanedCell::new();
+  /// let mut placed = pin!(cell.placed());
+  /// let mut cx = Context::from_waker(Waker::noop());
+  /// assert_eq!(placed.as_mut().poll(&mut cx), Poll::Pending);
+  /// cell.place(LoanedMut::new(1)).unwrap();
+  /// assert_eq!(placed.poll(&mut cx), Poll::Ready(()));
+  /// ```
+  #[inline]
+  pub fn placed(&self) -> Placed<'_, T> {
+    Placed(self)
+  }
+
+  /// Takes the value out of the cell, if one has been placed.
+  pub fn take(&mut self) -> Option<T> {
+    let state = self.state.get_mut().unwrap_or_else(|e| e.into_inner());
+    state
+      .value
+      .take()
+      .map(|value| unsafe { ptr::read(value.as_ref()) })
+  }
+
+  /// Consumes the cell, returning the value, if one has been placed.
+  #[inline]
+  pub fn into_inner(mut self) -> Option<T> {
+    self.take()
+  }
+
+  fn lock(&self) -> std::sync::MutexGuard<'_, CellState<T>> {
+    self.state.lock().unwrap_or_else(|e| e.into_inner())
+  }
+}
+
+impl<T> Default for LoanedCell<T> {
+  fn default() -> Self {
+    LoanedCell::new()
+  }
+}
+
+impl<T> Drop for LoanedCell<T> {
+  fn drop(&mut self) {
+    // Placing borrows the cell for `'t`, so `'t` must have expired by now.
+    self.take();
+  }
+}
+
+/// A future which resolves once a value has been placed into a [`LoanedCell`].
+///
+/// Returned by [`LoanedCell::placed`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Placed<'a, T>(&'a LoanedCell<T>);
+
+impl<'a, T> Future for Placed<'a, T> {
+  type Output = ();
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    let mut state = self.0.lock();
+    if state.value.is_some() {
+      return Poll::Ready(());
+    }
+    if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+      state.wakers.push(cx.waker().clone());
+    }
+    Poll::Pending
+  }
+}
@@ -0,0 +1,140 @@
+use crate::*;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// The receiving half of a channel of loaned values.
+///
+/// [`LoanedReceiver::sender`] borrows the receiver for `'t`, returning a
+/// [`SendPlace`] that loaned values can be sent through (e.g. from other
+/// threads). Since the receiver is borrowed, the values can only be received
+/// once `'t` has expired.
+///
+/// # Example
+/// ```
+/// use loaned::{LoanedMut, LoanedReceiver};
+/// let mut receiver = LoanedReceiver::new();
+/// let sender = receiver.sender();
+/// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+/// let (b, loaned_b) = LoanedMut::loan(Box::new(0));
+/// std::thread::scope(|s| {
+///   let sender_b = sender.clone();
+///   s.spawn(move || sender.send(loaned_a));
+///   s.spawn(move || sender_b.send(loaned_b));
+///   *a = 1;
+///   *b = 2;
+/// });
+/// let mut values = receiver.try_iter().collect::<Vec<_>>();
+/// values.sort();
+/// assert_eq!(values, [Box::new(1), Box::new(2)]);
+/// ```
+pub struct LoanedReceiver<T> {
+  sender: Sender<RawLoaned<T>>,
+  receiver: Receiver<RawLoaned<T>>,
+}
+
+impl<T> LoanedReceiver<T> {
+  /// Constructs a new, empty `LoanedReceiver`.
+  pub fn new() -> Self {
+    let (sender, receiver) = mpsc::channel();
+    LoanedReceiver { sender, receiver }
+  }
+
+  /// Borrows the receiver for `'t`, returning a place which loaned values can
+  /// be sent through.
+  #[inline]
+  pub fn sender<'t>(&'t self) -> SendPlace<'t, T> {
+    SendPlace {
+      sender: self.sender.clone(),
+      _receiver: PhantomData,
+    }
+  }
+
+  /// Receives a value, if one has been sent.
+  pub fn try_recv(&mut self) -> Option<T> {
+    let value = self.receiver.try_recv().ok()?;
+    Some(unsafe { MaybeUninit::from(value).assume_init() })
+  }
+
+  /// Returns an iterator over the values that have been sent.
+  #[inline]
+  pub fn try_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+    core::iter::from_fn(|| self.try_recv())
+  }
+}
+
+impl<T> Default for LoanedReceiver<T> {
+  fn default() -> Self {
+    LoanedReceiver::new()
+  }
+}
+
+impl<T> Drop for LoanedReceiver<T> {
+  fn drop(&mut self) {
+    // Sending borrows the receiver for `'t`, so `'t` must have expired by now.
+    self.try_iter().for_each(mem::drop);
+  }
+}
+
+/// A place which sends loaned values to a [`LoanedReceiver`].
+///
+/// Returned by [`LoanedReceiver::sender`].
+pub struct SendPlace<'t, T> {
+  sender: Sender<RawLoaned<T>>,
+  _receiver: PhantomData<&'t ()>,
+}
+
+impl<'t, T> SendPlace<'t, T> {
+  /// Sends a loaned value to the receiver.
+  pub fn send(&self, loaned: LoanedMut<'t, T>) {
+    // The receiver is borrowed for `'t`, so it can't have been dropped.
+    let _ = self.sender.send(loaned.into_raw());
+  }
+}
+
+/// Placing into a `SendPlace` sends the value to the receiver.
+///
+/// As with any place, the `SendPlace` is mutably borrowed for `'t`, so each
+/// placement uses up a sender; clone it to place multiple values.
+///
+/// # Example
+/// ```
+/// use loaned::{LoanedMut, LoanedReceiver};
+/// let mut receiver = LoanedReceiver::new();
+/// let sender = receiver.sender();
+/// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+/// let (b, loaned_b) = LoanedMut::loan(Box::new(0));
+/// let mut sender_a = sender.clone();
+/// let mut sender_b = sender;
+/// std::thread::scope(|s| {
+///   s.spawn(|| loaned_a.place(&mut sender_a));
+///   s.spawn(|| loaned_b.place(&mut sender_b));
+///   *a = 1;
+///   *b = 2;
+/// });
+/// let mut values = receiver.try_iter().collect::<Vec<_>>();
+/// values.sort();
+/// assert_eq!(values, [Box::new(1), Box::new(2)]);
+/// ```
+impl<'t, T> Place<'t, T> for SendPlace<'t, T> {
+  #[inline]
+  fn place(loaned: LoanedMut<'t, T>, place: &'t mut Self) {
+    place.send(loaned)
+  }
+}
+
+impl<'t, T> Clone for SendPlace<'t, T> {
+  fn clone(&self) -> Self {
+    SendPlace {
+      sender: self.sender.clone(),
+      _receiver: PhantomData,
+    }
+  }
+}
+
+crate::compile_fail_test!(recv_while_borrowed: "E0502" {
+  let mut receiver = loaned::LoanedReceiver::new();
+  let sender = receiver.sender();
+  let (borrow, loaned) = loaned::LoanedMut::loan(Box::new(0));
+  sender.send(loaned);
+  let _ = receiver.try_recv();
+  *borrow = 1;
+});
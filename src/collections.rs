@@ -0,0 +1,225 @@
+//! Collections built on loans.
+
+use crate::*;
+
+/// A singly-linked list, built with a [`ListBuilder`].
+///
+/// Each element is stored in its own allocation, so elements can borrow each
+/// other while the list is being built (e.g. to form cycles).
+///
+/// `List` has no `Drop` impl of its own (so that its elements may hold
+/// borrows of each other when it is dropped); dropping a very long list
+/// recurses once per element. Drain it with [`List::pop_front`] or
+/// [`IntoIterator::into_iter`] if that is a concern.
+///
+/// # Example
+/// ```
+/// use loaned::{collections::ListBuilder, drop};
+/// use std::cell::Cell;
+///
+/// struct Node<'a> {
+///   name: &'static str,
+///   next: Cell<Option<&'a Node<'a>>>,
+/// }
+///
+/// let mut builder = ListBuilder::new();
+/// let a = &*builder.push(Node { name: "a", next: Cell::new(None) });
+/// let b = &*builder.push(Node { name: "b", next: Cell::new(None) });
+/// a.next.set(Some(b));
+/// b.next.set(Some(a));
+/// assert_eq!(a.next.get().unwrap().next.get().unwrap().name, "a");
+/// drop!(builder.finish());
+/// ```
+pub struct List<T> {
+  head: Option<Box<ListNode<T>>>,
+  len: usize,
+}
+
+struct ListNode<T> {
+  value: T,
+  next: Option<Box<ListNode<T>>>,
+}
+
+impl<T> List<T> {
+  /// Constructs a new, empty `List`.
+  #[inline]
+  pub const fn new() -> Self {
+    List { head: None, len: 0 }
+  }
+
+  /// Returns the number of elements in the list.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns `true` if the list has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Adds an element to the front of the list.
+  pub fn push_front(&mut self, value: T) {
+    let next = self.head.take();
+    self.head = Some(Box::new(ListNode { value, next }));
+    self.len += 1;
+  }
+
+  /// Removes the first element of the list, if any.
+  pub fn pop_front(&mut self) -> Option<T> {
+    let node = self.head.take()?;
+    let ListNode { value, next } = *node;
+    self.head = next;
+    self.len -= 1;
+    Some(value)
+  }
+
+  /// Returns an iterator over the elements of the list.
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, T> {
+    Iter(self.head.as_deref())
+  }
+
+  /// Returns an iterator over mutable references to the elements of the list.
+  #[inline]
+  pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+    IterMut(self.head.as_deref_mut())
+  }
+}
+
+impl<T> Default for List<T> {
+  fn default() -> Self {
+    List::new()
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for List<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+/// An iterator over the elements of a [`List`].
+pub struct Iter<'a, T>(Option<&'a ListNode<T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+  type Item = &'a T;
+  fn next(&mut self) -> Option<&'a T> {
+    let node = self.0?;
+    self.0 = node.next.as_deref();
+    Some(&node.value)
+  }
+}
+
+/// An iterator over mutable references to the elements of a [`List`].
+pub struct IterMut<'a, T>(Option<&'a mut ListNode<T>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+  type Item = &'a mut T;
+  fn next(&mut self) -> Option<&'a mut T> {
+    let node = self.0.take()?;
+    self.0 = node.next.as_deref_mut();
+    Some(&mut node.value)
+  }
+}
+
+/// An owning iterator over the elements of a [`List`].
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+  type Item = T;
+  fn next(&mut self) -> Option<T> {
+    self.0.pop_front()
+  }
+}
+
+impl<T> IntoIterator for List<T> {
+  type Item = T;
+  type IntoIter = IntoIter<T>;
+  fn into_iter(self) -> IntoIter<T> {
+    IntoIter(self)
+  }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+  type Item = &'a T;
+  type IntoIter = Iter<'a, T>;
+  fn into_iter(self) -> Iter<'a, T> {
+    self.iter()
+  }
+}
+
+/// Builds a [`List`] by appending elements, handing out an `&'t mut` borrow of
+/// each element as it is added.
+///
+/// # Example
+/// ```
+/// use loaned::{collections::ListBuilder, take};
+/// let mut builder = ListBuilder::new();
+/// let a = builder.push(1);
+/// let b = builder.push(2);
+/// *a += *b;
+/// let list = take!(builder.finish());
+/// assert_eq!(list.into_iter().collect::<Vec<_>>(), [3, 2]);
+/// ```
+pub struct ListBuilder<'t, T> {
+  list: LoanedMut<'t, Box<List<T>>>,
+  tail: &'t mut Option<Box<ListNode<T>>>,
+  len: &'t mut usize,
+}
+
+impl<'t, T> ListBuilder<'t, T> {
+  /// Constructs a new `ListBuilder` for an empty list.
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn new() -> Self {
+    let (list, loaned) = LoanedMut::loan(Box::new(List::new()));
+    let List { head, len } = list;
+    ListBuilder {
+      list: loaned,
+      tail: head,
+      len,
+    }
+  }
+
+  /// Appends an element to the list, returning a mutable borrow of it.
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn push(&mut self, value: T) -> &'t mut T {
+    let (node, loaned) = LoanedMut::loan(Box::new(ListNode { value, next: None }));
+    let ListNode { value, next } = node;
+    let tail = mem::replace(&mut self.tail, next);
+    LoanedMut::<Option<Box<_>>>::from(Some(loaned)).place(tail);
+    *self.len += 1;
+    value
+  }
+
+  /// Returns the number of elements pushed so far.
+  #[inline]
+  pub fn len(&self) -> usize {
+    *self.len
+  }
+
+  /// Returns `true` if no elements have been pushed.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    *self.len == 0
+  }
+
+  /// Finishes building, returning the loaned list.
+  #[inline]
+  pub fn finish(self) -> LoanedMut<'t, Box<List<T>>> {
+    self.list
+  }
+}
+
+impl<'t, T> Default for ListBuilder<'t, T> {
+  fn default() -> Self {
+    ListBuilder::new()
+  }
+}
+
+impl<'t, T> core::fmt::Debug for ListBuilder<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "ListBuilder(..)")
+  }
+}
@@ -2,14 +2,27 @@ use crate::*;
 
 macro_rules! main_impls {
   ($Loaned:ident) => {
-    #[cfg(feature = "alloc")]
+    #[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
     impl<'t, T> From<Box<$Loaned<'t, T>>> for $Loaned<'t, Box<T>> {
       fn from(value: Box<$Loaned<'t, T>>) -> Self {
         unsafe { $Loaned::new(Box::from_raw(Box::into_raw(value) as *mut _)) }
       }
     }
 
-    #[cfg(feature = "alloc")]
+    #[cfg(feature = "allocator_api")]
+    impl<'t, T, A: core::alloc::Allocator> From<Box<$Loaned<'t, T>, A>> for $Loaned<'t, Box<T, A>> {
+      fn from(value: Box<$Loaned<'t, T>, A>) -> Self {
+        unsafe {
+          let (ptr, alloc) = Box::into_raw_with_allocator(value);
+          $Loaned::new(Box::from_raw_in(ptr as *mut _, alloc))
+        }
+      }
+    }
+
+    /// Reuses the allocation of the `Vec`, preserving its length and capacity
+    /// (including for zero-sized `T`). Since loans have the same size and
+    /// alignment as `T`, this can't fail.
+    #[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
     impl<'t, T> From<Vec<$Loaned<'t, T>>> for $Loaned<'t, Vec<T>> {
       fn from(value: Vec<$Loaned<'t, T>>) -> Self {
         unsafe {
@@ -23,9 +36,298 @@ macro_rules! main_impls {
       }
     }
 
+    /// Reuses the allocation of the `Vec`, preserving its length and capacity
+    /// (including for zero-sized `T`). Since loans have the same size and
+    /// alignment as `T`, this can't fail.
+    #[cfg(feature = "allocator_api")]
+    impl<'t, T, A: core::alloc::Allocator> From<Vec<$Loaned<'t, T>, A>> for $Loaned<'t, Vec<T, A>> {
+      fn from(value: Vec<$Loaned<'t, T>, A>) -> Self {
+        unsafe {
+          let (ptr, len, capacity, alloc) = value.into_raw_parts_with_alloc();
+          $Loaned::new(Vec::from_raw_parts_in(ptr as *mut _, len, capacity, alloc))
+        }
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t, T> FromIterator<$Loaned<'t, T>> for $Loaned<'t, Vec<T>> {
+      fn from_iter<I: IntoIterator<Item = $Loaned<'t, T>>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t, T> From<$Loaned<'t, Box<[T]>>> for $Loaned<'t, Vec<T>> {
+      fn from(value: $Loaned<'t, Box<[T]>>) -> Self {
+        // The slice may be loaned, so the `Box` can't be used as a `Box` (which
+        // would assert its uniqueness); instead, its raw parts are read out of
+        // the `RawLoaned`, which has the same layout as `*mut [T]`.
+        unsafe {
+          let ptr: *mut [T] = mem::transmute_copy(&value.into_raw());
+          $Loaned::new(Vec::from_raw_parts(ptr as *mut T, ptr.len(), ptr.len()))
+        }
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t, T> $Loaned<'t, Vec<T>> {
+      /// Converts the loaned `Vec` into a boxed slice, without reallocating.
+      ///
+      /// If the `Vec` has excess capacity, it is returned instead, as shrinking
+      /// it could move its elements (which may be loaned).
+      pub fn into_boxed_slice(self) -> Result<$Loaned<'t, Box<[T]>>, Self> {
+        let vec = unsafe { self.inner.as_ref() };
+        if mem::size_of::<T>() != 0 && vec.len() != vec.capacity() {
+          return Err(self);
+        }
+        // As above, the `Box` is built from its raw parts directly inside the
+        // `RawLoaned`, so it's never used as a `Box`.
+        let ptr = ptr::slice_from_raw_parts_mut(vec.as_ptr() as *mut T, vec.len());
+        let _ = self.into_raw();
+        unsafe { Ok($Loaned::from_raw(mem::transmute_copy(&ptr))) }
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t, T> From<Box<[$Loaned<'t, T>]>> for $Loaned<'t, Box<[T]>> {
+      fn from(value: Box<[$Loaned<'t, T>]>) -> Self {
+        unsafe { $Loaned::new(Box::from_raw(Box::into_raw(value) as *mut [T])) }
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t> From<$Loaned<'t, Box<str>>> for $Loaned<'t, alloc::string::String> {
+      fn from(value: $Loaned<'t, Box<str>>) -> Self {
+        // As with `Box<[T]>`, the `Box` is never used as a `Box`.
+        unsafe {
+          let ptr = mem::transmute_copy::<_, *mut str>(&value.into_raw()) as *mut [u8];
+          $Loaned::new(alloc::string::String::from_raw_parts(
+            ptr as *mut u8,
+            ptr.len(),
+            ptr.len(),
+          ))
+        }
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t> $Loaned<'t, alloc::string::String> {
+      /// Converts the loaned `String` into a boxed `str`, without reallocating.
+      ///
+      /// If the `String` has excess capacity, it is returned instead, as
+      /// shrinking it could move its contents (which may be loaned). For the
+      /// same reason, there is no infallible `From` conversion; to ensure the
+      /// conversion succeeds, convert the `String` into a `Box<str>` before
+      /// loaning it instead.
+      ///
+      /// # Example
+      /// ```
+      /// use loaned::{take, LoanedMut};
+      /// let (s, loaned) = LoanedMut::loan(String::from("hi"));
+      /// let loaned = loaned.into_boxed_str().unwrap();
+      /// s.make_ascii_uppercase();
+      /// assert_eq!(take!(loaned), Box::from("HI"));
+      /// ```
+      pub fn into_boxed_str(self) -> Result<$Loaned<'t, Box<str>>, Self> {
+        let string = unsafe { self.inner.as_ref() };
+        if string.len() != string.capacity() {
+          return Err(self);
+        }
+        // Moving the `String` (unlike a `Box`) doesn't assert anything about its
+        // contents, so its raw parts can be read from the underlying `Vec`.
+        unsafe {
+          let value: alloc::string::String = MaybeUninit::from(self.into_raw()).assume_init();
+          let mut bytes = ManuallyDrop::new(value.into_bytes());
+          let ptr = ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), bytes.len()) as *mut str;
+          Ok($Loaned::from_raw(mem::transmute_copy(&ptr)))
+        }
+      }
+    }
+
+    /// Moves the loaned values into a `SmallVec` of the same inline capacity.
+    /// The values themselves may be moved (e.g. if they are stored inline), but
+    /// their pointees are not, so the loaned borrows remain valid.
+    #[cfg(feature = "smallvec")]
+    impl<'t, T, const N: usize> From<smallvec::SmallVec<[$Loaned<'t, T>; N]>>
+      for $Loaned<'t, smallvec::SmallVec<[T; N]>>
+    {
+      fn from(value: smallvec::SmallVec<[$Loaned<'t, T>; N]>) -> Self {
+        let vec: $Loaned<'t, Vec<T>> = value.into_vec().into();
+        unsafe {
+          let vec: Vec<T> = MaybeUninit::from(vec.into_raw()).assume_init();
+          $Loaned::new(smallvec::SmallVec::from_vec(vec))
+        }
+      }
+    }
+
+    impl<'t, T> From<Option<$Loaned<'t, T>>> for $Loaned<'t, Option<T>> {
+      fn from(value: Option<$Loaned<'t, T>>) -> Self {
+        match value {
+          None => $Loaned::new(None),
+          Some(value) => unsafe {
+            let value = place::_maybe_uninit_some(value.into_raw().into());
+            $Loaned::from_raw(mem::transmute_copy(&value))
+          },
+        }
+      }
+    }
+
+    impl<'t, T> From<$Loaned<'t, Option<T>>> for Option<$Loaned<'t, T>> {
+      fn from(value: $Loaned<'t, Option<T>>) -> Self {
+        unsafe {
+          let value = ManuallyDrop::new(value.into_raw());
+          let ptr = &*value as *const RawLoaned<Option<T>> as *const Option<T>;
+          let ptr = (*ptr).as_ref()? as *const T;
+          Some($Loaned::from_raw(ptr::read(ptr.cast::<RawLoaned<T>>())))
+        }
+      }
+    }
+
+    impl<'t, T> $Loaned<'t, Option<T>> {
+      /// Converts a loaned `Option` into an optional loan, without ending the
+      /// loan. The inverse is available via `From`.
+      ///
+      /// # Example
+      /// ```
+      /// use loaned::{take, LoanedMut};
+      /// let (a, loaned) = LoanedMut::loan(Box::new(1));
+      /// let loaned = LoanedMut::<Option<Box<_>>>::from(Some(loaned));
+      /// let loaned = loaned.transpose().unwrap();
+      /// *a = 2;
+      /// assert_eq!(take!(loaned), Box::new(2));
+      /// ```
+      pub fn transpose(self) -> Option<$Loaned<'t, T>> {
+        self.into()
+      }
+    }
+
+    impl<'t, T, E> From<Result<$Loaned<'t, T>, E>> for $Loaned<'t, Result<T, E>> {
+      fn from(value: Result<$Loaned<'t, T>, E>) -> Self {
+        match value {
+          Err(err) => $Loaned::new(Err(err)),
+          Ok(value) => unsafe {
+            let value = place::_maybe_uninit_ok::<T, E>(value.into_raw().into());
+            $Loaned::from_raw(mem::transmute_copy(&value))
+          },
+        }
+      }
+    }
+
+    impl<'t, T, E> From<Result<$Loaned<'t, T>, $Loaned<'t, E>>> for $Loaned<'t, Result<T, E>> {
+      fn from(value: Result<$Loaned<'t, T>, $Loaned<'t, E>>) -> Self {
+        unsafe {
+          let value = match value {
+            Ok(value) => place::_maybe_uninit_ok::<T, E>(value.into_raw().into()),
+            Err(err) => place::_maybe_uninit_err::<T, E>(err.into_raw().into()),
+          };
+          $Loaned::from_raw(mem::transmute_copy(&value))
+        }
+      }
+    }
+
+    impl<'t, T, E> From<$Loaned<'t, Result<T, E>>> for Result<$Loaned<'t, T>, $Loaned<'t, E>> {
+      fn from(value: $Loaned<'t, Result<T, E>>) -> Self {
+        unsafe {
+          let value = ManuallyDrop::new(value.into_raw());
+          let ptr = &*value as *const RawLoaned<Result<T, E>> as *const Result<T, E>;
+          match &*ptr {
+            Ok(value) => Ok($Loaned::from_raw(ptr::read(
+              (value as *const T).cast::<RawLoaned<T>>(),
+            ))),
+            Err(err) => Err($Loaned::from_raw(ptr::read(
+              (err as *const E).cast::<RawLoaned<E>>(),
+            ))),
+          }
+        }
+      }
+    }
+
+    impl<'t, T, E> $Loaned<'t, Result<T, E>> {
+      /// Converts a loaned `Result` into a `Result` of loans, without ending
+      /// the loan. The inverse is available via `From`.
+      ///
+      /// # Example
+      /// ```
+      /// use loaned::{take, LoanedMut};
+      /// let (a, loaned) = LoanedMut::loan(Box::new(1));
+      /// let loaned = LoanedMut::<Result<Box<_>, ()>>::from(Ok::<_, ()>(loaned));
+      /// let loaned = loaned.transpose().unwrap();
+      /// *a = 2;
+      /// assert_eq!(take!(loaned), Box::new(2));
+      /// ```
+      pub fn transpose(self) -> Result<$Loaned<'t, T>, $Loaned<'t, E>> {
+        self.into()
+      }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'t, T> $Loaned<'t, Box<T>> {
+      #[doc(hidden)]
+      pub unsafe fn __unsize<U: ?Sized>(
+        self,
+        f: impl FnOnce(*mut T) -> *mut U,
+      ) -> $Loaned<'t, Box<U>> {
+        let raw = ManuallyDrop::new(self.into_raw());
+        let ptr = f(ptr::read(
+          (&*raw as *const RawLoaned<Box<T>>).cast::<*mut T>(),
+        ));
+        $Loaned::from_raw(mem::transmute_copy::<*mut U, RawLoaned<Box<U>>>(&ptr))
+      }
+    }
+
+    impl<'t, T, const N: usize> $Loaned<'t, [T; N]> {
+      /// Merges an array of loans. This is the same as the `From` impl, but
+      /// usable in const contexts.
+      #[inline(always)]
+      pub const fn from_array(value: [$Loaned<'t, T>; N]) -> Self {
+        let value = ManuallyDrop::new(value);
+        unsafe { ptr::read(&value as *const ManuallyDrop<[$Loaned<'t, T>; N]> as *const Self) }
+      }
+
+      /// Splits a loaned array into an array of loans. This is the same as the
+      /// `From` impl, but usable in const contexts.
+      #[inline(always)]
+      pub const fn into_array(self) -> [$Loaned<'t, T>; N] {
+        let value = ManuallyDrop::new(self);
+        unsafe { ptr::read(&value as *const ManuallyDrop<Self> as *const [$Loaned<'t, T>; N]) }
+      }
+    }
+
     impl<'t, T, const N: usize> From<[$Loaned<'t, T>; N]> for $Loaned<'t, [T; N]> {
       fn from(value: [$Loaned<'t, T>; N]) -> Self {
-        unsafe { mem::transmute_copy(&ManuallyDrop::new(value)) }
+        $Loaned::from_array(value)
+      }
+    }
+
+    impl<'t, T, const N: usize> From<$Loaned<'t, [T; N]>> for [$Loaned<'t, T>; N] {
+      fn from(value: $Loaned<'t, [T; N]>) -> Self {
+        value.into_array()
+      }
+    }
+
+    impl<'t, T> $Loaned<'t, T> {
+      /// Wraps the loaned value in a [`TransparentWrapper`] (e.g. a newtype),
+      /// without ending the loan.
+      #[inline(always)]
+      pub fn wrap<W: TransparentWrapper<T>>(self) -> $Loaned<'t, W> {
+        unsafe {
+          $Loaned::from_raw(mem::transmute_copy::<RawLoaned<T>, RawLoaned<W>>(
+            &self.into_raw(),
+          ))
+        }
+      }
+
+      /// Unwraps a loaned [`TransparentWrapper`], without ending the loan.
+      #[inline(always)]
+      pub fn unwrap_inner<U>(self) -> $Loaned<'t, U>
+      where
+        T: TransparentWrapper<U>,
+      {
+        unsafe {
+          $Loaned::from_raw(mem::transmute_copy::<RawLoaned<T>, RawLoaned<U>>(
+            &self.into_raw(),
+          ))
+        }
       }
     }
 
@@ -43,6 +345,45 @@ macro_rules! main_impls {
   };
 }
 
+/// Converts a `Loaned<'t, Box<T>>` or `LoanedMut<'t, Box<T>>` into a loan of a
+/// `Box<U>`, where `Box<T>` can be coerced to `Box<U>` (e.g. `U` is a trait
+/// object implemented by `T`), without ending the loan.
+///
+/// # Example
+/// ```
+/// use loaned::{take, unsize, LoanedMut};
+/// use std::fmt::Debug;
+///
+/// let (a, loaned_a) = LoanedMut::loan(Box::new(1));
+/// let (b, loaned_b) = LoanedMut::loan(Box::new("b"));
+/// let nodes: Vec<LoanedMut<Box<dyn Debug>>> =
+///   vec![unsize!(loaned_a, dyn Debug), unsize!(loaned_b, dyn Debug)];
+/// *a = 2;
+/// *b = "c";
+/// let nodes = take!(LoanedMut::<Vec<Box<_>>>::from(nodes));
+/// assert_eq!(format!("{nodes:?}"), "[2, \"c\"]");
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! unsize {
+  ($loaned:expr, $U:ty) => {{
+    let loaned = $loaned;
+    // SAFETY: the closure can only return `ptr` coerced to `*mut $U` (not cast).
+    unsafe {
+      loaned.__unsize(|ptr| {
+        let ptr: *mut $U = ptr;
+        ptr
+      })
+    }
+  }};
+}
+
+#[cfg(feature = "alloc")]
+crate::compile_fail_test!(unsize_cast: "E0308" {
+  let loaned = loaned::LoanedMut::new(Box::new(1u32));
+  let _ = loaned::unsize!(loaned, u64);
+});
+
 main_impls!(Loaned);
 main_impls!(LoanedMut);
 
@@ -62,8 +403,130 @@ macro_rules! tuple_impls {
         }
       }
     }
+
+    impl<'t, $($T),*> From<$Loaned<'t, ($($T,)*)>> for ($($Loaned<'t, $T>,)*) {
+      fn from(value: $Loaned<'t, ($($T,)*)>) -> Self {
+        unsafe {
+          let value = ManuallyDrop::new(value.into_raw());
+          let ptr = &*value as *const RawLoaned<($($T,)*)> as *const ($($T,)*);
+          ($($Loaned::from_raw(ptr::read(ptr::addr_of!((*ptr).$i).cast::<RawLoaned<$T>>())),)*)
+        }
+      }
+    }
   };
 }
 
 tuple_impls!(Loaned [] 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J);
 tuple_impls!(LoanedMut [] 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J);
+
+/// Values which can be converted into a `LoanedMut<'t, T>`: a `LoanedMut`
+/// itself, a [`Loaned`], or a plain `T` (which isn't loaned).
+///
+/// This allows [`LoanedMut::from_parts`] to merge tuples mixing loans and
+/// plain values.
+pub trait IntoLoanedMut<'t, T> {
+  #[allow(missing_docs)]
+  fn into_loaned_mut(self) -> LoanedMut<'t, T>;
+}
+
+impl<'t, T> IntoLoanedMut<'t, T> for T {
+  #[inline(always)]
+  fn into_loaned_mut(self) -> LoanedMut<'t, T> {
+    LoanedMut::new(self)
+  }
+}
+
+impl<'t, T> IntoLoanedMut<'t, T> for LoanedMut<'t, T> {
+  #[inline(always)]
+  fn into_loaned_mut(self) -> LoanedMut<'t, T> {
+    self
+  }
+}
+
+impl<'t, T> IntoLoanedMut<'t, T> for Loaned<'t, T> {
+  #[inline(always)]
+  fn into_loaned_mut(self) -> LoanedMut<'t, T> {
+    self.into()
+  }
+}
+
+/// Tuples whose elements each implement [`IntoLoanedMut`]; see
+/// [`LoanedMut::from_parts`].
+pub trait MergeParts<'t, T> {
+  #[allow(missing_docs)]
+  fn merge_parts(self) -> LoanedMut<'t, T>;
+}
+
+impl<'t, T> LoanedMut<'t, T> {
+  /// Merges a tuple mixing loans ([`LoanedMut`]s and [`Loaned`]s) and plain
+  /// values into a loan of a tuple, like the `From` impl for tuples of
+  /// `LoanedMut`s.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned, LoanedMut};
+  /// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+  /// let (c, loaned_c) = Loaned::loan(Box::new(3));
+  /// let abc = LoanedMut::<(Box<u32>, u32, Box<u32>)>::from_parts((loaned_a, 2, loaned_c));
+  /// *a = *c - 2;
+  /// assert_eq!(take!(abc), (Box::new(1), 2, Box::new(3)));
+  /// ```
+  #[inline]
+  pub fn from_parts(parts: impl MergeParts<'t, T>) -> Self {
+    parts.merge_parts()
+  }
+}
+
+macro_rules! parts_impls {
+  ([$($x:tt)*] $i:tt $T:ident $X:ident $($y:tt)*) => {
+    parts_impls!($($x)* $i $T $X);
+    parts_impls!([$($x)* $i $T $X] $($y)*);
+  };
+  ([$($x:tt)*]) => {};
+  ($($i:tt $T:ident $X:ident)+) => {
+    impl<'t, $($T, $X: IntoLoanedMut<'t, $T>),*> MergeParts<'t, ($($T,)*)> for ($($X,)*) {
+      #[inline]
+      fn merge_parts(self) -> LoanedMut<'t, ($($T,)*)> {
+        ($(self.$i.into_loaned_mut(),)*).into()
+      }
+    }
+  };
+}
+
+parts_impls!([] 0 A XA 1 B XB 2 C XC 3 D XD 4 E XE 5 F XF 6 G XG 7 H XH 8 I XI 9 J XJ);
+
+/// Types which are `#[repr(transparent)]` wrappers around a `T`, allowing a
+/// loaned `T` to be wrapped with [`LoanedMut::wrap`] (or [`Loaned::wrap`]) and
+/// unwrapped with [`LoanedMut::unwrap_inner`] without ending the loan.
+///
+/// Wrapping by value (e.g. with a closure taking the `T`) isn't possible, as
+/// moving a `T` (like a `Box`) asserts that nothing else is accessing its
+/// pointee. For wrappers with other fields, use [`LoanedMut::from_parts`] or
+/// [`LoanedMut::merge`] instead.
+///
+/// # Safety
+///
+/// `Self` must be `#[repr(transparent)]` over `T`, and every valid `T` must be
+/// a valid `Self`. `Self` must not allow the `T` to be replaced or mutated
+/// through a shared reference (so e.g. `Cell<T>` can't implement this), as
+/// that would allow a [`Loaned`] value to be dropped while it is borrowed.
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, TransparentWrapper};
+///
+/// #[repr(transparent)]
+/// struct Node(Box<u32>);
+/// unsafe impl TransparentWrapper<Box<u32>> for Node {}
+///
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+/// let node: LoanedMut<Node> = loaned.wrap();
+/// *borrow += 1;
+/// assert_eq!(*take!(node).0, 2);
+/// ```
+pub unsafe trait TransparentWrapper<T> {}
+
+unsafe impl<T> TransparentWrapper<T> for ManuallyDrop<T> {}
+unsafe impl<T> TransparentWrapper<T> for core::num::Wrapping<T> {}
+// Without `Unpin`, a mutable borrow of the pointee could be used to move it.
+unsafe impl<P: Deref> TransparentWrapper<P> for Pin<P> where P::Target: Unpin {}
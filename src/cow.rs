@@ -0,0 +1,111 @@
+use crate::*;
+use alloc::borrow::{Cow, ToOwned};
+
+/// A [`Cow`] which is guaranteed to be [`Cow::Owned`].
+///
+/// Unlike `Cow`, this implements [`Loanable`] (when `B::Owned` does), as it
+/// dereferences through `B::Owned` rather than through
+/// [`Borrow`](core::borrow::Borrow).
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, OwnedCow};
+/// use std::borrow::Cow;
+/// let cow: Cow<str> = Cow::Owned(String::from("hello"));
+/// let (borrow, loaned) = LoanedMut::loan(OwnedCow::try_from(cow).unwrap());
+/// borrow.make_ascii_uppercase();
+/// assert_eq!(take!(loaned).into_cow(), "HELLO");
+/// assert!(OwnedCow::try_from(Cow::Borrowed("hello")).is_err());
+/// ```
+pub struct OwnedCow<'a, B: ?Sized + ToOwned>(Cow<'a, B>);
+
+impl<'a, B: ?Sized + ToOwned> OwnedCow<'a, B> {
+  /// Constructs an `OwnedCow` from an owned value.
+  #[inline]
+  pub fn new(owned: B::Owned) -> Self {
+    OwnedCow(Cow::Owned(owned))
+  }
+
+  /// Returns the underlying `Cow`, which is always [`Cow::Owned`].
+  #[inline]
+  pub fn into_cow(self) -> Cow<'a, B> {
+    self.0
+  }
+
+  /// Returns the owned value.
+  #[inline]
+  pub fn into_owned(self) -> B::Owned {
+    self.0.into_owned()
+  }
+
+  #[inline]
+  fn owned(&self) -> &B::Owned {
+    match &self.0 {
+      Cow::Owned(owned) => owned,
+      Cow::Borrowed(_) => unreachable!(),
+    }
+  }
+
+  #[inline]
+  fn owned_mut(&mut self) -> &mut B::Owned {
+    match &mut self.0 {
+      Cow::Owned(owned) => owned,
+      Cow::Borrowed(_) => unreachable!(),
+    }
+  }
+}
+
+impl<'a, B: ?Sized + ToOwned> TryFrom<Cow<'a, B>> for OwnedCow<'a, B> {
+  type Error = Cow<'a, B>;
+
+  /// Converts a `Cow` into an `OwnedCow`, returning it back if it is
+  /// [`Cow::Borrowed`].
+  fn try_from(cow: Cow<'a, B>) -> Result<Self, Self::Error> {
+    match cow {
+      Cow::Owned(_) => Ok(OwnedCow(cow)),
+      Cow::Borrowed(_) => Err(cow),
+    }
+  }
+}
+
+impl<'a, B: ?Sized + ToOwned> From<OwnedCow<'a, B>> for Cow<'a, B> {
+  fn from(value: OwnedCow<'a, B>) -> Self {
+    value.0
+  }
+}
+
+impl<'a, B: ?Sized + ToOwned> Deref for OwnedCow<'a, B>
+where
+  B::Owned: Deref<Target = B>,
+{
+  type Target = B;
+
+  fn deref(&self) -> &B {
+    self.owned()
+  }
+}
+
+impl<'a, B: ?Sized + ToOwned> DerefMut for OwnedCow<'a, B>
+where
+  B::Owned: DerefMut<Target = B>,
+{
+  fn deref_mut(&mut self) -> &mut B {
+    self.owned_mut()
+  }
+}
+
+impl<'a, B: ?Sized + ToOwned> core::fmt::Debug for OwnedCow<'a, B>
+where
+  B::Owned: core::fmt::Debug,
+{
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.owned().fmt(f)
+  }
+}
+
+// `OwnedCow` always dereferences through `B::Owned`, so it is exactly as
+// stable as `B::Owned`.
+unsafe impl<'t, 'a, B: ?Sized + ToOwned> Loanable<'t> for OwnedCow<'a, B> where
+  B::Owned: Loanable<'t, Target = B>
+{
+}
@@ -0,0 +1,93 @@
+use crate::*;
+
+/// Collects [`Loaned`] and [`LoanedMut`] values, and drops all of them at once
+/// when the queue itself is dropped with [`drop!`] (which statically ensures
+/// that `'t` has expired).
+///
+/// This is useful on `no_std` targets, where dropping a loaned value silently
+/// leaks it instead of panicking. Dropping a `DropQueue` without [`drop!`]
+/// behaves like dropping a [`LoanedMut`].
+///
+/// # Example
+/// ```
+/// use loaned::{drop, DropQueue, Loaned, LoanedMut};
+/// let mut queue = DropQueue::new();
+/// let (a, loaned) = LoanedMut::loan(Box::new(1));
+/// queue.push(loaned);
+/// let (b, loaned) = Loaned::loan(Box::new(2));
+/// queue.push(loaned);
+/// *a += *b;
+/// assert_eq!(*a, 3);
+/// drop!(queue); // drops both boxes
+/// ```
+#[must_use = "dropping a `DropQueue` panics; use `loaned::drop!` instead"]
+pub struct DropQueue<'t>(LoanedMut<'t, Vec<DeferredDrop>>);
+
+/// A type-erased value registered in a [`DropQueue`], which is dropped along
+/// with it.
+pub struct DeferredDrop(Box<dyn ErasedDrop>);
+
+trait ErasedDrop {
+  unsafe fn drop_in_place(&mut self);
+}
+
+impl<T> ErasedDrop for MaybeUninit<T> {
+  unsafe fn drop_in_place(&mut self) {
+    self.assume_init_drop()
+  }
+}
+
+impl Drop for DeferredDrop {
+  fn drop(&mut self) {
+    unsafe { self.0.drop_in_place() }
+  }
+}
+
+impl<'t> DropQueue<'t> {
+  /// Constructs a new, empty `DropQueue`.
+  #[inline]
+  pub fn new() -> Self {
+    DropQueue(LoanedMut::new(Vec::new()))
+  }
+
+  /// Registers a loaned value, to be dropped along with the queue.
+  ///
+  /// The value must be `'static`, as it is dropped after `'t` has expired.
+  pub fn push<T: 'static>(&mut self, loaned: impl Placeable<'t, T>) {
+    let mut slot = Box::new(MaybeUninit::<T>::uninit());
+    loaned.place(unsafe { &mut *(&mut *slot as *mut MaybeUninit<T>) });
+    self.entries().push(DeferredDrop(slot));
+  }
+
+  /// Returns the number of values in the queue.
+  #[inline]
+  pub fn len(&self) -> usize {
+    unsafe { self.0.inner.as_ref() }.len()
+  }
+
+  /// Returns `true` if the queue has no values.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  #[inline]
+  fn entries(&mut self) -> &mut Vec<DeferredDrop> {
+    // The entries themselves are never loaned; only their contents are.
+    unsafe { self.0.inner.as_mut() }
+  }
+}
+
+impl<'t> Default for DropQueue<'t> {
+  fn default() -> Self {
+    DropQueue::new()
+  }
+}
+
+impl<'t> LoanWrapper<'t> for DropQueue<'t> {
+  type Value = Vec<DeferredDrop>;
+  #[inline]
+  fn into_loan(self) -> LoanedMut<'t, Vec<DeferredDrop>> {
+    self.0
+  }
+}
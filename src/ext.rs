@@ -0,0 +1,53 @@
+use crate::*;
+use alloc::{rc::Rc, sync::Arc};
+
+mod sealed {
+  pub trait Sealed {}
+}
+
+/// Extension methods for loaning smart pointers directly, as in
+/// `Box::new(x).loan_mut()`.
+///
+/// These are equivalent to [`Loaned::loan`] and [`LoanedMut::loan`]; like
+/// those, `'t` is a parameter of the method, so it is inferred from how the
+/// borrow and loan are used.
+///
+/// This trait is sealed, and is implemented for `Box`, `Rc`, and `Arc`.
+///
+/// # Example
+/// ```
+/// use loaned::prelude::*;
+/// use std::rc::Rc;
+/// let (a, loaned_a) = Box::new(1).loan_mut();
+/// let (b, loaned_b) = Rc::new(2).loan();
+/// *a += *b;
+/// assert_eq!(take!(loaned_a), Box::new(3));
+/// assert_eq!(*take!(loaned_b), 2);
+/// ```
+pub trait LoanExt: Deref + Sized + sealed::Sealed {
+  /// Immutably loans `self`, like [`Loaned::loan`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  fn loan<'t>(self) -> (&'t Self::Target, Loaned<'t, Self>)
+  where
+    Self: Loanable<'t>,
+  {
+    Loaned::loan(self)
+  }
+
+  /// Mutably loans `self`, like [`LoanedMut::loan`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  fn loan_mut<'t>(self) -> (&'t mut Self::Target, LoanedMut<'t, Self>)
+  where
+    Self: Loanable<'t> + DerefMut,
+  {
+    LoanedMut::loan(self)
+  }
+}
+
+impl<T: ?Sized> sealed::Sealed for Box<T> {}
+impl<T: ?Sized> sealed::Sealed for Rc<T> {}
+impl<T: ?Sized> sealed::Sealed for Arc<T> {}
+
+impl<T: ?Sized> LoanExt for Box<T> {}
+impl<T: ?Sized> LoanExt for Rc<T> {}
+impl<T: ?Sized> LoanExt for Arc<T> {}
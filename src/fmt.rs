@@ -0,0 +1,42 @@
+//! Formatting helpers for loaned values.
+//!
+//! [`Loaned`]'s `Debug` impl formats the loaned pointer, wrapped in
+//! `Loaned(..)`; [`DebugBorrowed`] formats its pointee directly, as a borrow of
+//! it would be formatted. [`LoanedMut`]'s `Debug` impl is opaque, as its
+//! pointee can't be accessed.
+//!
+//! # Example
+//! ```
+//! use loaned::{drop, fmt::DebugBorrowed, Loaned};
+//! let nodes = Loaned::new(vec![Box::new(1), Box::new(2)]);
+//! assert_eq!(format!("{nodes:?}"), "Loaned([1, 2])");
+//! assert_eq!(format!("{:?}", DebugBorrowed(&nodes)), "[1, 2]");
+//! let node = Loaned::new(Box::new((1, "a")));
+//! assert_eq!(format!("{:#?}", DebugBorrowed(&node)), "(\n    1,\n    \"a\",\n)");
+//! drop!(nodes);
+//! drop!(node);
+//! ```
+
+use crate::*;
+use core::fmt::{Debug, Formatter, Result};
+
+/// Formats the pointee of a [`Loaned`], rather than the loaned pointer. See
+/// the [module documentation](self).
+pub struct DebugBorrowed<'a, 't, T>(pub &'a Loaned<'t, T>);
+
+impl<'a, 't, T: Deref> Debug for DebugBorrowed<'a, 't, T>
+where
+  T::Target: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    (***self.0).fmt(f)
+  }
+}
+
+impl<'a, 't, T> Clone for DebugBorrowed<'a, 't, T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a, 't, T> Copy for DebugBorrowed<'a, 't, T> {}
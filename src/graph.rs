@@ -0,0 +1,88 @@
+//! A builder for graphs of boxed nodes, built on loans.
+
+use crate::*;
+
+/// Builds a graph of boxed nodes, handing out an `&'t mut` handle to each node
+/// as it is added, so that nodes can be wired together in any order.
+///
+/// Edges that own their targets can be filled by placing loaned values into
+/// holes within nodes (see the `tree_building` example). Other edges,
+/// including cycles, can be expressed with the indices returned by
+/// [`GraphBuilder::add`], which correspond to the positions of the nodes in the
+/// finished `Vec`.
+///
+/// # Example
+/// ```
+/// use loaned::{graph::GraphBuilder, take};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Node {
+///   value: u32,
+///   next: usize,
+/// }
+///
+/// let mut graph = GraphBuilder::new();
+/// let (a, a_node) = graph.add(Node { value: 1, next: 0 });
+/// let (b, b_node) = graph.add(Node { value: 2, next: a });
+/// a_node.next = b;
+/// b_node.value += a_node.value;
+/// let nodes = take!(graph.finish());
+/// assert_eq!(nodes[a].next, b);
+/// assert_eq!(nodes[nodes[a].next].value, 3);
+/// ```
+pub struct GraphBuilder<'t, N> {
+  nodes: LoanedVec<'t, Box<N>>,
+}
+
+impl<'t, N> GraphBuilder<'t, N> {
+  /// Constructs a new, empty `GraphBuilder`.
+  #[inline]
+  pub const fn new() -> Self {
+    GraphBuilder {
+      nodes: LoanedVec::new(),
+    }
+  }
+
+  /// Adds a node to the graph, returning its index along with a mutable
+  /// borrow of it.
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn add(&mut self, node: N) -> (usize, &'t mut N) {
+    let (borrow, loaned) = LoanedMut::loan(Box::new(node));
+    (self.add_loaned(loaned), borrow)
+  }
+
+  /// Adds an already-loaned node to the graph, returning its index.
+  pub fn add_loaned(&mut self, node: LoanedMut<'t, Box<N>>) -> usize {
+    let index = self.nodes.len();
+    self.nodes.push(node);
+    index
+  }
+
+  /// Returns the number of nodes in the graph.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Returns `true` if the graph has no nodes.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Finishes building the graph, returning its nodes in the order they were
+  /// added.
+  ///
+  /// The nodes can be taken with [`take!`] once all of the handles have
+  /// expired.
+  #[inline]
+  pub fn finish(self) -> LoanedMut<'t, Vec<Box<N>>> {
+    self.nodes.into()
+  }
+}
+
+impl<'t, N> Default for GraphBuilder<'t, N> {
+  fn default() -> Self {
+    GraphBuilder::new()
+  }
+}
@@ -0,0 +1,106 @@
+use crate::*;
+use core::ptr::NonNull;
+
+/// An FFI-safe handle to a mutably loaned `Box<T>`, represented as a thin
+/// pointer to the box's pointee.
+///
+/// `LoanHandle<'t, T>` is `#[repr(transparent)]` over `NonNull<T>`, so it can
+/// be passed to and from foreign code as a non-null `T*`. It owns the
+/// allocation, like the [`LoanedMut`] it was created from; dropping it
+/// behaves like dropping a `LoanedMut`. Foreign code holding the pointer must
+/// not access the pointee while it is loaned, and must hand the handle back
+/// (e.g. by returning it from a callback) rather than freeing it.
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanHandle, LoanedMut};
+///
+/// extern "C" fn callback(handle: LoanHandle<u32>) -> LoanHandle<u32> {
+///   handle
+/// }
+///
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+/// let handle = callback(LoanHandle::new(loaned));
+/// *borrow += 1;
+/// assert_eq!(take!(handle.into_loaned()), Box::new(2));
+/// ```
+#[must_use = "dropping a `LoanHandle` panics; use `loaned::drop!` instead"]
+#[repr(transparent)]
+pub struct LoanHandle<'t, T> {
+  ptr: NonNull<T>,
+  _loaned: PhantomData<LoanedMut<'t, Box<T>>>,
+}
+
+impl<'t, T> LoanHandle<'t, T> {
+  /// Converts a loaned `Box` into a handle.
+  #[inline]
+  pub fn new(loaned: LoanedMut<'t, Box<T>>) -> Self {
+    let raw = loaned.into_raw();
+    LoanHandle {
+      ptr: unsafe { mem::transmute_copy::<RawLoaned<Box<T>>, NonNull<T>>(&raw) },
+      _loaned: PhantomData,
+    }
+  }
+
+  /// Converts the handle back into a loaned `Box`.
+  #[inline]
+  pub fn into_loaned(self) -> LoanedMut<'t, Box<T>> {
+    let ptr = self.into_raw();
+    unsafe { LoanedMut::from_raw(mem::transmute_copy::<*mut T, RawLoaned<Box<T>>>(&ptr)) }
+  }
+
+  /// Converts the handle into a raw pointer to the loaned allocation. The
+  /// pointee must not be accessed for the duration of `'t`.
+  #[inline]
+  pub fn into_raw(self) -> *mut T {
+    ManuallyDrop::new(self).ptr.as_ptr()
+  }
+
+  /// Converts a raw pointer back into a handle.
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must have been returned by [`LoanHandle::into_raw`] on a handle
+  /// with the same `T` and a lifetime outliving `'t`, and must not have been
+  /// converted back already.
+  #[inline]
+  pub unsafe fn from_raw(ptr: *mut T) -> Self {
+    LoanHandle {
+      ptr: NonNull::new_unchecked(ptr),
+      _loaned: PhantomData,
+    }
+  }
+}
+
+impl<'t, T> From<LoanedMut<'t, Box<T>>> for LoanHandle<'t, T> {
+  #[inline]
+  fn from(loaned: LoanedMut<'t, Box<T>>) -> Self {
+    LoanHandle::new(loaned)
+  }
+}
+
+impl<'t, T> From<LoanHandle<'t, T>> for LoanedMut<'t, Box<T>> {
+  #[inline]
+  fn from(handle: LoanHandle<'t, T>) -> Self {
+    handle.into_loaned()
+  }
+}
+
+impl<'t, T> LoanWrapper<'t> for LoanHandle<'t, T> {
+  type Value = Box<T>;
+  #[inline]
+  fn into_loan(self) -> LoanedMut<'t, Box<T>> {
+    self.into_loaned()
+  }
+}
+
+// Like `LoanedMut<'t, Box<T>>`.
+unsafe impl<'t, T: Send> Send for LoanHandle<'t, T> {}
+unsafe impl<'t, T: Sync> Sync for LoanHandle<'t, T> {}
+
+impl<'t, T> Drop for LoanHandle<'t, T> {
+  fn drop(&mut self) {
+    let ptr = self.ptr.as_ptr();
+    drop(unsafe { LoanedMut::from_raw(mem::transmute_copy::<*mut T, RawLoaned<Box<T>>>(&ptr)) })
+  }
+}
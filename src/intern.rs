@@ -0,0 +1,120 @@
+//! A string interner built on loans.
+
+use crate::*;
+use alloc::collections::BTreeMap;
+
+/// An interned string, which can be resolved with [`Interner::resolve`].
+///
+/// Symbols are assigned in the order strings are first interned, and
+/// correspond to their positions in the `Vec` returned by
+/// [`Interner::into_inner`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Symbol(usize);
+
+impl Symbol {
+  /// Returns the index of the symbol.
+  #[inline]
+  pub fn index(self) -> usize {
+    self.0
+  }
+}
+
+/// Interns strings, loaning out an `&'t str` for each distinct string while
+/// retaining ownership of them.
+///
+/// # Example
+/// ```
+/// use loaned::{intern::Interner, take};
+///
+/// let mut interner = Interner::new();
+/// let a = interner.get_or_intern("a");
+/// let b = interner.get_or_intern(String::from("b"));
+/// assert_eq!(interner.get_or_intern("a"), a);
+/// let s: &str = interner.resolve(b);
+/// assert_eq!(s, "b");
+/// assert_eq!(interner.get("c"), None);
+/// let strings = take!(interner.into_inner());
+/// assert_eq!(strings, [Box::from("a"), Box::from("b")]);
+/// ```
+pub struct Interner<'t> {
+  strings: LoanedVec<'t, Box<str>>,
+  symbols: BTreeMap<&'t str, Symbol>,
+  resolved: Vec<&'t str>,
+}
+
+impl<'t> Interner<'t> {
+  /// Constructs a new, empty `Interner`.
+  #[inline]
+  pub const fn new() -> Self {
+    Interner {
+      strings: LoanedVec::new(),
+      symbols: BTreeMap::new(),
+      resolved: Vec::new(),
+    }
+  }
+
+  /// Returns the symbol for `string`, interning it if it hasn't been already.
+  ///
+  /// The string is only converted into a `Box<str>` if it is new.
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn get_or_intern(&mut self, string: impl AsRef<str> + Into<Box<str>>) -> Symbol {
+    if let Some(symbol) = self.get(string.as_ref()) {
+      return symbol;
+    }
+    let (borrow, loaned) = Loaned::loan(string.into());
+    let symbol = Symbol(self.resolved.len());
+    self.strings.push(loaned.into());
+    self.symbols.insert(borrow, symbol);
+    self.resolved.push(borrow);
+    symbol
+  }
+
+  /// Interns `string`, returning a borrow of the interned string.
+  #[inline]
+  pub fn intern(&mut self, string: impl AsRef<str> + Into<Box<str>>) -> &'t str {
+    let symbol = self.get_or_intern(string);
+    self.resolve(symbol)
+  }
+
+  /// Returns the symbol for `string`, if it has been interned.
+  #[inline]
+  pub fn get(&self, string: &str) -> Option<Symbol> {
+    self.symbols.get(string).copied()
+  }
+
+  /// Returns the string for `symbol`.
+  ///
+  /// # Panics
+  /// Panics if `symbol` was not returned by this interner.
+  #[inline]
+  pub fn resolve(&self, symbol: Symbol) -> &'t str {
+    self.resolved[symbol.0]
+  }
+
+  /// Returns the number of interned strings.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.resolved.len()
+  }
+
+  /// Returns `true` if no strings have been interned.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.resolved.is_empty()
+  }
+
+  /// Returns the interned strings, indexed by their symbols.
+  ///
+  /// The strings can be taken with [`take!`] once all of the borrows have
+  /// expired.
+  #[inline]
+  pub fn into_inner(self) -> LoanedMut<'t, Vec<Box<str>>> {
+    self.strings.into()
+  }
+}
+
+impl<'t> Default for Interner<'t> {
+  fn default() -> Self {
+    Interner::new()
+  }
+}
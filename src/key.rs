@@ -0,0 +1,68 @@
+use crate::*;
+
+/// The address of a loaned allocation, for use as a key in maps and sets
+/// which need identity semantics (rather than the value equality of
+/// [`Loaned`]'s `PartialEq`).
+///
+/// Keys can be obtained from loans ([`Loaned::key`], or [`LoanedMut::key`] for
+/// boxes) and from the borrows loaned out of them ([`LoanKey::of`]), which
+/// compare equal.
+///
+/// Only the address is compared; a key doesn't keep the allocation alive, so
+/// keys of allocations that have since been freed may collide with new ones.
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanKey, Loaned};
+/// use std::{collections::HashMap, rc::Rc};
+/// let (borrow, a) = Loaned::loan(Rc::new(1));
+/// let b = Loaned::new(Rc::new(1));
+/// assert_eq!(a, b);
+/// assert!(!a.ptr_eq(&b));
+/// let mut names = HashMap::new();
+/// names.insert(a.key(), "a");
+/// names.insert(b.key(), "b");
+/// assert_eq!(names[&LoanKey::of(borrow)], "a");
+/// assert_eq!(take!(a), Rc::new(1));
+/// assert_eq!(take!(b), Rc::new(1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct LoanKey(usize);
+
+impl LoanKey {
+  /// Returns the key of the allocation `value` points into.
+  #[inline]
+  pub fn of<T: ?Sized>(value: *const T) -> Self {
+    LoanKey(value.cast::<()>() as usize)
+  }
+
+  /// Returns the address of the allocation.
+  #[inline]
+  pub fn addr(self) -> usize {
+    self.0
+  }
+}
+
+impl<'t, T: Deref> Loaned<'t, T> {
+  /// Returns a [`LoanKey`] identifying the pointee of the value.
+  #[inline]
+  pub fn key(&self) -> LoanKey {
+    LoanKey::of(self.as_ptr())
+  }
+
+  /// Returns `true` if both values point to the same allocation, like
+  /// [`Rc::ptr_eq`](alloc::rc::Rc::ptr_eq).
+  #[inline]
+  pub fn ptr_eq<U: Deref>(&self, other: &Loaned<'_, U>) -> bool {
+    self.key() == other.key()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T: ?Sized> LoanedMut<'t, Box<T>> {
+  /// Returns a [`LoanKey`] identifying the pointee of the loaned `Box`.
+  #[inline]
+  pub fn key(&self) -> LoanKey {
+    LoanKey::of(self.as_ptr())
+  }
+}
@@ -1,6 +1,7 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -8,27 +9,118 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 use alloc::{boxed::Box, vec::Vec};
 
+#[cfg(feature = "derive")]
+pub use loaned_derive::{LoanProject, Place};
+
 use core::{
   marker::PhantomData,
   mem::{self, ManuallyDrop, MaybeUninit},
   ops::{Deref, DerefMut},
+  pin::Pin,
   ptr,
 };
 
+#[cfg(feature = "alloc")]
+pub mod arena;
+pub mod brand;
+#[cfg(feature = "std")]
+mod cell;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "alloc")]
+pub mod collections;
 mod convert;
+#[cfg(feature = "alloc")]
+mod cow;
+#[cfg(feature = "alloc")]
+mod drop_queue;
+#[cfg(feature = "alloc")]
+mod ext;
+pub mod fmt;
+#[cfg(feature = "alloc")]
+pub mod graph;
+#[cfg(feature = "alloc")]
+mod handle;
+#[cfg(feature = "alloc")]
+pub mod intern;
+mod key;
+mod loan_cell;
+#[cfg(feature = "alloc")]
+mod loan_scope;
 mod loanable;
 mod loaned;
 mod loaned_mut;
+mod origin;
+#[cfg(feature = "std")]
+pub mod par;
 mod place;
-mod raw_loaned;
+pub mod policy;
+pub mod prelude;
+mod project;
+pub mod raw;
+#[cfg(feature = "alloc")]
+mod rc;
+#[cfg(feature = "std")]
+mod scope;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod slice;
+#[cfg(feature = "alloc")]
+pub mod slot;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(not(feature = "stats"))]
+mod stats;
+#[cfg(feature = "std")]
+pub mod sync;
 mod take;
+pub mod testutil;
+#[cfg(feature = "alloc")]
+mod uninit;
+#[cfg(feature = "alloc")]
+mod vec;
 
+#[cfg(feature = "std")]
+pub use cell::*;
+#[cfg(feature = "std")]
+pub use channel::*;
+pub use convert::{IntoLoanedMut, MergeParts, TransparentWrapper};
+#[cfg(feature = "alloc")]
+pub use cow::*;
+#[cfg(feature = "alloc")]
+pub use drop_queue::*;
+#[cfg(feature = "alloc")]
+pub use ext::*;
+#[cfg(feature = "alloc")]
+pub use handle::*;
+pub use key::*;
+pub use loan_cell::*;
+#[cfg(feature = "alloc")]
+pub use loan_scope::*;
 pub use loanable::*;
 pub use loaned::*;
 pub use loaned_mut::*;
+#[cfg(feature = "debug-loans")]
+pub use origin::loan_origin;
+#[doc(hidden)]
+pub use origin::{__LoanOrigins, __ReleaseNoOrigins, __ReleaseOrigins, __ReleaseSliceOrigins};
+use origin::{record_loan, release_taken, Placements};
+#[cfg(feature = "rayon")]
+pub use par::__par_drop;
 pub use place::*;
-use raw_loaned::*;
+use policy::{DropPolicy, Panic};
+pub use project::*;
+use raw::*;
+#[cfg(feature = "alloc")]
+pub use rc::*;
+#[cfg(feature = "std")]
+pub use scope::*;
+pub use slice::*;
 pub use take::*;
+#[cfg(feature = "alloc")]
+pub use uninit::*;
+#[cfg(feature = "alloc")]
+pub use vec::*;
 
 #[cfg(all(test, feature = "std"))]
 mod test {
@@ -45,6 +137,20 @@ mod test {
     assert_eq!(*x, 2);
   }
 
+  #[test]
+  fn loan_into() {
+    let mut storage = Box::new(MaybeUninit::uninit());
+    let ptr = storage.as_ptr();
+    for i in 0..3 {
+      let (r, b) = LoanedMut::loan_into(storage, 0);
+      *r = i;
+      let x = take!(b);
+      assert_eq!(*x, i);
+      storage = unsafe { Box::from_raw(Box::into_raw(x).cast()) };
+      assert_eq!(storage.as_ptr(), ptr);
+    }
+  }
+
   #[test]
   fn loaned_atomic() {
     let (r, b) = Loaned::loan(Box::new(AtomicU32::new(0)));
@@ -58,6 +164,67 @@ mod test {
     assert_eq!(*x.as_mut().get_mut(), 12);
   }
 
+  #[test]
+  fn downgrade() {
+    let (r, b) = LoanedMut::loan(vec![1, 2]);
+    r[1] = 3;
+    let b = b.downgrade(r);
+    assert_eq!(b.borrow(), &[1, 3]);
+    assert_eq!(take!(b), vec![1, 3]);
+  }
+
+  #[test]
+  #[should_panic = "must be passed the borrow of the entire pointee"]
+  fn downgrade_partial() {
+    let mut x = [1, 2];
+    let (r, b) = LoanedMut::loan(&mut x[..]);
+    let (r, _) = r.split_at_mut(1);
+    let _ = b.downgrade(r);
+  }
+
+  #[test]
+  fn try_take() {
+    let (r1, b1) = LoanedMut::loan(Box::new(1));
+    let (r2, b2) = LoanedMut::loan(Box::new(2));
+    *r2 = 3;
+    let b1 = b1.try_take(r2).unwrap_err();
+    *r1 = 4;
+    assert_eq!(b1.try_take(r1).ok(), Some(Box::new(4)));
+    assert_eq!(take!(b2), Box::new(3));
+  }
+
+  #[test]
+  fn loaned_arc() {
+    use std::sync::Arc;
+    let (r, a): (_, LoanedArc<_>) = Loaned::loan(Arc::new(AtomicU32::new(0)));
+    let b = a.clone();
+    let handles = (0..4)
+      .map(|_| {
+        let c = Arc::clone(&a);
+        std::thread::spawn(move || c.fetch_add(1, Ordering::Relaxed))
+      })
+      .collect::<Vec<_>>();
+    for handle in handles {
+      handle.join().unwrap();
+    }
+    r.fetch_add(1, Ordering::Relaxed);
+    drop!(a);
+    assert_eq!(Arc::into_inner(take!(b)).unwrap().into_inner(), 5);
+  }
+
+  #[test]
+  #[cfg(feature = "stable_deref")]
+  fn loan_stable_deref() {
+    let x = std::sync::Mutex::new(1);
+    let (r, b) = LoanedMut::loan(StableLoanable(x.lock().unwrap()));
+    *r = 2;
+    core::mem::drop(take!(b));
+    assert_eq!(*x.lock().unwrap(), 2);
+    let (r, b) = LoanedMut::loan(Box::pin(3));
+    *r = 4;
+    assert_eq!(*take!(b), 4);
+  }
+
   #[test]
   fn place_option() {
     let (r, b) = LoanedMut::loan(Box::new(123));
@@ -68,6 +235,45 @@ mod test {
     assert_eq!(x, Some(Box::new(2)));
   }
 
+  #[test]
+  fn place_vec() {
+    let mut x = vec![Box::new(0)];
+    let mut place = VecPlace::new(&mut x);
+    let mut borrows = vec![];
+    for i in 1..10 {
+      let (r, b) = LoanedMut::loan(Box::new(i));
+      place.push(b);
+      borrows.push(r);
+    }
+    assert_eq!(place.len(), 10);
+    for r in borrows {
+      *r *= 2;
+    }
+    assert_eq!(x, (0..10).map(|i| Box::new(i * 2)).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn place_hash_map() {
+    use std::collections::HashMap;
+    let mut x = HashMap::from([(0, None)]);
+    let mut y = None;
+    let mut place = HashMapPlace::new(&mut x);
+    let mut borrows = vec![];
+    for i in 0..10 {
+      let (r, b) = LoanedMut::loan(Box::new(i));
+      place.insert(i, b).unwrap();
+      borrows.push(r);
+    }
+    let b = place.insert(0, LoanedMut::new(Box::new(0))).unwrap_err();
+    b.place(&mut y);
+    for r in borrows {
+      *r *= 2;
+    }
+    assert_eq!(x.len(), 10);
+    assert!(x.iter().all(|(k, v)| v == &Some(Box::new(k * 2))));
+    assert_eq!(y, Some(Box::new(0)));
+  }
+
   #[test]
   fn take() {
     let (r, b) = LoanedMut::loan(Box::new(123));
@@ -89,6 +295,98 @@ mod test {
     assert_eq!(take!(a), (Box::new(1), Box::new(2)));
   }
 
+  #[test]
+  fn from_tuple() {
+    let ((r1, r2), x) = LoanedMut::loan_with((Box::new(0), Box::new(0)), |x, l| {
+      (l.loan_mut(&mut x.0), l.loan_mut(&mut x.1))
+    });
+    let (a, b) = x.into();
+    *r1 = 1;
+    *r2 = 2;
+    let x: LoanedMut<(_, _)> = (b, a).into();
+    assert_eq!(take!(x), (Box::new(2), Box::new(1)));
+  }
+
+  #[test]
+  fn from_array() {
+    let (r, x) = LoanedMut::loan_with([Box::new(0), Box::new(0), Box::new(0)], |x, l| {
+      x.each_mut().map(|x| l.loan_mut(x))
+    });
+    let [a, b, c]: [_; 3] = x.into();
+    for (i, r) in r.into_iter().enumerate() {
+      *r = i;
+    }
+    let x: LoanedMut<[Box<_>; 3]> = [c, b, a].into();
+    assert_eq!(take!(x), [Box::new(2), Box::new(1), Box::new(0)]);
+  }
+
+  #[test]
+  fn leak() {
+    let (r, b) = LoanedMut::loan(Box::new(1));
+    let b = leak!(b);
+    *r = 2;
+    assert_eq!(take!(ManuallyDrop::into_inner(b)), Box::new(2));
+    // `Vec::new` doesn't allocate, so this doesn't actually leak memory.
+    let (r, b) = Loaned::loan(Vec::<u32>::new());
+    let _: ManuallyDrop<_> = b.into();
+    assert!(r.is_empty());
+  }
+
+  #[test]
+  fn forget() {
+    let (r, b) = LoanedMut::loan(Vec::<u32>::new());
+    b.forget();
+    assert!(r.is_empty());
+    let (r, b) = Loaned::loan(String::new());
+    b.forget();
+    assert!(r.is_empty());
+  }
+
+  #[test]
+  fn loan_cow() {
+    use std::borrow::Cow;
+    let (r, b) = Loaned::loan_cow(Cow::Borrowed("abc"));
+    assert_eq!(r, "abc");
+    assert_eq!(take!(b), String::from("abc"));
+    let (r, b) = LoanedMut::loan_cow(Cow::<[u32]>::Owned(vec![1, 2]));
+    r[0] = 3;
+    assert_eq!(take!(b), vec![3, 2]);
+  }
+
+  #[test]
+  fn downcast() {
+    use std::any::Any;
+    let (r, b) = Loaned::loan(Box::new(AtomicU32::new(1)) as Box<dyn Any + Send>);
+    let b = b.downcast::<u32>().unwrap_err();
+    let b = b.downcast::<AtomicU32>().unwrap();
+    let r = r.downcast_ref::<AtomicU32>().unwrap();
+    r.fetch_add(1, Ordering::Relaxed);
+    b.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(take!(b).into_inner(), 3);
+  }
+
+  #[test]
+  fn map() {
+    let (r, b) = Loaned::loan_with((1, Box::new(2)), |x, l| l.loan(&x.1));
+    let b = b.map(|x| &x.1);
+    assert_eq!(*r, 2);
+    assert_eq!(take!(b), Box::new(2));
+  }
+
+  #[test]
+  #[should_panic = "must return a reference to a field"]
+  fn map_outside() {
+    let _ = Loaned::new((1, 2)).map(|_| &0);
+  }
+
+  #[test]
+  fn map_unchecked() {
+    let (r, b) = LoanedMut::loan_with((Box::new(1), 2), |x, l| l.loan_mut(&mut x.0));
+    let b = unsafe { b.map_unchecked(|x| &mut x.0) };
+    *r = 3;
+    assert_eq!(take!(b), Box::new(3));
+  }
+
   #[test]
   fn into_box() {
     let (r, b) = LoanedMut::loan(Box::new(123));
@@ -114,6 +412,229 @@ mod test {
     assert_eq!(take!(x), vec![Box::new(1), Box::new(2), Box::new(3)]);
   }
 
+  #[test]
+  fn loaned_vec() {
+    let mut x = LoanedVec::new();
+    let mut borrows = vec![];
+    for i in 0..10 {
+      let (r, b) = LoanedMut::loan(Box::new(i));
+      x.push(b);
+      borrows.push(r);
+    }
+    x.reserve(10);
+    assert!(x.capacity() >= 20);
+    x.extend((10..20).map(|i| LoanedMut::new(Box::new(i))));
+    let x = LoanedMut::<Vec<_>>::from(x);
+    for r in borrows {
+      *r *= 2;
+    }
+    let expected = (0..20).map(|i| Box::new(if i < 10 { i * 2 } else { i }));
+    assert_eq!(take!(x), expected.collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn drop_queue() {
+    static DROPPED: AtomicU32 = AtomicU32::new(0);
+    struct Foo(u32);
+    impl Drop for Foo {
+      fn drop(&mut self) {
+        DROPPED.fetch_add(self.0, Ordering::Relaxed);
+      }
+    }
+    let mut queue = DropQueue::new();
+    let (a, b) = LoanedMut::loan(Box::new(Foo(1)));
+    queue.push(b);
+    let (c, d) = Loaned::loan(Box::new(Foo(2)));
+    queue.push(d);
+    queue.push(LoanedMut::new(Foo(4)));
+    assert_eq!(queue.len(), 3);
+    a.0 += c.0;
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+    drop!(queue);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 9);
+
+    let (mut x, mut y) = (DropQueue::new(), DropQueue::new());
+    x.push(LoanedMut::new(Foo(16)));
+    y.push(LoanedMut::new(Foo(32)));
+    drop_all!([x, y]);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 57);
+
+    let mut queue = DropQueue::new();
+    queue.push(LoanedMut::new(Foo(64)));
+    let merged = LoanedMut::merge(Vec::new(), |entries, m| m.place(queue, entries));
+    assert_eq!(take!(merged).len(), 1);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 121);
+  }
+
+  #[test]
+  fn loaned_cell() {
+    use std::{
+      future::Future,
+      pin::pin,
+      sync::Arc,
+      task::{Context, Wake},
+      thread::{self, Thread},
+    };
+    struct Unpark(Thread);
+    impl Wake for Unpark {
+      fn wake(self: Arc<Self>) {
+        self.0.unpark();
+      }
+    }
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut cell = LoanedCell::new();
+    let mut rejected = None;
+    let (r, b) = LoanedMut::loan(Box::new(0));
+    thread::scope(|s| {
+      let cell = &cell;
+      let mut placed = pin!(cell.placed());
+      s.spawn(move || {
+        thread::sleep(std::time::Duration::from_millis(10));
+        cell.place(b).unwrap();
+      });
+      *r = 1;
+      while placed.as_mut().poll(&mut cx).is_pending() {
+        thread::park();
+      }
+      assert!(cell.is_placed());
+      cell
+        .place(LoanedMut::new(Box::new(3)))
+        .unwrap_err()
+        .place(&mut rejected);
+      *r += 1;
+    });
+    assert_eq!(cell.take(), Some(Box::new(2)));
+    assert_eq!(cell.take(), None);
+    assert_eq!(rejected, Some(Box::new(3)));
+  }
+
+  #[test]
+  fn slice_builder() {
+    let mut builder = SliceBuilder::new(10);
+    let mut borrows = vec![];
+    for i in (0..10).rev() {
+      let (r, b) = LoanedMut::loan(Box::new(i));
+      builder.place(i, b).unwrap();
+      borrows.push(r);
+      assert!(builder.is_placed(i));
+    }
+    assert!(!builder.is_placed(10));
+    let x = builder.finish().unwrap();
+    for r in borrows {
+      *r *= 2;
+    }
+    assert_eq!(
+      *take!(x),
+      *(0..10).map(|i| Box::new(i * 2)).collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  #[should_panic = "memory leak"]
+  fn slice_builder_unfinished() {
+    let mut builder = SliceBuilder::new(2);
+    builder.place(0, LoanedMut::new(String::new())).unwrap();
+    let _ = builder.finish().unwrap_err();
+  }
+
+  #[test]
+  fn arena() {
+    let mut arena = arena::Arena::with_capacity(1);
+    let mut borrows = vec![];
+    for i in 0..10 {
+      borrows.push(arena.alloc(i));
+    }
+    let slice = arena.alloc_iter(10..20);
+    assert_eq!(arena.len(), 20);
+    for r in borrows {
+      *r *= 2;
+    }
+    slice.reverse();
+    let chunks = take!(arena.into_inner());
+    assert_eq!(
+      chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+      [1, 2, 4, 3, 10]
+    );
+    let values = chunks.into_iter().flatten().collect::<Vec<_>>();
+    let expected = (0..10).map(|i| i * 2).chain((10..20).rev());
+    assert_eq!(values, expected.collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn graph_builder() {
+    struct Node {
+      value: u32,
+      edges: Vec<usize>,
+    }
+    let mut graph = graph::GraphBuilder::new();
+    let nodes = (0..5)
+      .map(|value| {
+        graph.add(Node {
+          value,
+          edges: vec![],
+        })
+      })
+      .collect::<Vec<_>>();
+    for (i, (_, node)) in nodes.into_iter().enumerate() {
+      node.edges.push((i + 1) % 5);
+      node.value *= 2;
+    }
+    assert_eq!(graph.len(), 5);
+    let nodes = take!(graph.finish());
+    let mut i = 0;
+    for _ in 0..10 {
+      assert_eq!(nodes[i].value, i as u32 * 2);
+      i = nodes[i].edges[0];
+    }
+    assert_eq!(i, 0);
+  }
+
+  #[test]
+  fn take_unchecked() {
+    let (r, a) = LoanedMut::loan(Box::new(0));
+    *r = 1;
+    assert_eq!(unsafe { a.take_unchecked() }, Box::new(1));
+    let (r, b) = Loaned::loan(Box::new(2));
+    assert_eq!(*r, 2);
+    unsafe { b.drop_unchecked() };
+  }
+
+  #[test]
+  fn vec_boxed_slice() {
+    let (r, b) = LoanedMut::loan(Box::new(0));
+    let x = LoanedMut::<Vec<Box<_>>>::from(vec![b, LoanedMut::new(Box::new(1))]);
+    let x = x.into_boxed_slice().unwrap();
+    *r = 2;
+    let x = LoanedMut::<Vec<_>>::from(x);
+    let mut y = Vec::with_capacity(2);
+    y.push(0);
+    let (s, y) = LoanedMut::loan(y);
+    // `y` has excess capacity, and its elements are loaned.
+    let y = y.into_boxed_slice().unwrap_err();
+    s[0] = 3;
+    assert_eq!(take!(x), [Box::new(2), Box::new(1)]);
+    assert_eq!(take!(y), [3]);
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serde() {
+    let (r, b) = Loaned::loan(Box::new(vec![1, 2]));
+    assert_eq!(serde_json::to_string(&b).unwrap(), "[1,2]");
+    assert_eq!(r, &[1, 2]);
+    let c: Loaned<Box<Vec<u32>>> = serde_json::from_str("[3]").unwrap();
+    assert_eq!(take!(c), Box::new(vec![3]));
+    assert_eq!(take!(b), Box::new(vec![1, 2]));
+  }
+
+  #[test]
+  #[should_panic = "must return a pointer to a field"]
+  fn place_field_outside() {
+    let mut x = LoanedMut::<MaybeUninit<(u8, u32)>>::uninit();
+    x.place_field(LoanedMut::new(0u32), |p| unsafe { p.cast::<u32>().add(2) });
+  }
+
   #[test]
   fn into_tuple() {
     let (r, b) = LoanedMut::loan(Box::new(0));
@@ -121,4 +642,614 @@ mod test {
     *r = 123;
     assert_eq!(take!(xy), (Box::new(123), Box::new(456)));
   }
+
+  #[test]
+  fn swap_place() {
+    let mut x = vec![0];
+    let (r, loaned) = LoanedMut::loan(vec![1]);
+    let old = loaned.swap_place(&mut x);
+    r[0] = 2;
+    assert_eq!(old, vec![0]);
+    assert_eq!(x, vec![2]);
+  }
+
+  #[test]
+  fn transpose_option() {
+    let none = LoanedMut::<Option<Box<u32>>>::from(None::<LoanedMut<_>>);
+    assert!(none.transpose().is_none());
+    let (r, b) = LoanedMut::loan(Box::new(1));
+    let b = LoanedMut::<Option<Box<_>>>::from(Some(b));
+    *r = 2;
+    let b = b.transpose().unwrap();
+    *r = 3;
+    let mut x = None;
+    LoanedMut::<Option<Box<_>>>::from(Some(b)).place(&mut x);
+    *r = 4;
+    assert_eq!(x, Some(Some(Box::new(4))));
+  }
+
+  #[test]
+  fn loan_slab() {
+    use slot::LoanSlab;
+    let mut x = None;
+    let mut slab = LoanSlab::new();
+    let (a, _) = slab.loan(vec![1]);
+    let (b, _) = slab.loan(vec![2]);
+    assert_eq!(slab.len(), 2);
+    let removed = slab.remove(a).unwrap();
+    assert!(slab.remove(a).is_none());
+    assert!(!slab.contains(a) && slab.contains(b));
+    assert_eq!(slab.get(a), None);
+    assert_eq!(slab.get(b), Some(&[2][..]));
+    assert_eq!(slab.len(), 1);
+    removed.place(&mut x);
+    drop!(slab.into_inner());
+    assert_eq!(x, Some(vec![1]));
+  }
+
+  #[test]
+  fn drop_policy() {
+    let (r, b) = LoanedMut::loan(Box::new(1));
+    let b = b.with_policy::<policy::Leak>();
+    *r = 2;
+    assert_eq!(take!(b), Box::new(2));
+    let (_, b) = Loaned::loan(Box::new(1));
+    core::mem::drop(b.with_policy::<policy::Leak>());
+  }
+
+  #[test]
+  fn unsize_loaned() {
+    let (r, b) = Loaned::loan(Box::new(AtomicU32::new(0)));
+    let b = unsize!(b, dyn core::any::Any);
+    r.store(1, Ordering::Relaxed);
+    let b = take!(b).downcast::<AtomicU32>().unwrap();
+    assert_eq!(b.into_inner(), 1);
+  }
+
+  #[test]
+  fn merge_no_drop() {
+    let (r1, b1) = LoanedMut::loan(Box::new(0));
+    let (r2, b2) = LoanedMut::loan(vec![0]);
+    let a = LoanedMut::<(Box<i32>, Vec<i32>)>::merge(Default::default(), |x, m| {
+      m.place(b1, &mut x.0);
+      m.place_no_drop(b2, &mut x.1);
+    });
+    *r1 = 1;
+    r2[0] = 2;
+    assert_eq!(take!(a), (Box::new(1), vec![2]));
+  }
+
+  #[test]
+  fn loan_str_ranges() {
+    let s = || String::from("aé-b");
+    assert!(LoanedMut::loan_str_ranges(s(), &[0..2, 3..4]).is_err());
+    assert!(LoanedMut::loan_str_ranges(s(), &[0..3, 2..4]).is_err());
+    assert!(LoanedMut::loan_str_ranges(s(), &[0..1, 4..6]).is_err());
+    assert!(LoanedMut::loan_str_ranges(s(), &[0..1, 3..6]).is_err());
+    let (ranges, b) = LoanedMut::loan_str_ranges(s(), &[3..4, 0..3, 4..5]).unwrap();
+    assert_eq!(
+      ranges.iter().map(|r| &**r).collect::<Vec<_>>(),
+      ["-", "aé", "b"]
+    );
+    for r in ranges {
+      r.make_ascii_uppercase();
+    }
+    assert_eq!(take!(b), "Aé-B");
+  }
+
+  #[test]
+  fn loan_guards() {
+    let cell = core::cell::RefCell::new(1);
+    let (r, b) = LoanedMut::loan(cell.borrow_mut());
+    *r = 2;
+    drop!(b);
+    let mutex = std::sync::Mutex::new(vec![1]);
+    let (r, b) = LoanedMut::loan(mutex.lock().unwrap());
+    r.push(2);
+    core::mem::drop(take!(b));
+    assert_eq!(
+      (cell.into_inner(), mutex.into_inner().unwrap()),
+      (2, vec![1, 2])
+    );
+    let (r, b) = Loaned::loan(Box::<str>::from("abc"));
+    assert_eq!(r, "abc");
+    drop!(b);
+  }
+
+  #[test]
+  #[cfg(feature = "triomphe")]
+  fn loan_triomphe() {
+    let (r, b) = Loaned::loan(triomphe::Arc::new(1));
+    let c = b.clone();
+    assert_eq!(*r, 1);
+    drop!(b);
+    assert_eq!(
+      triomphe::Arc::try_unique(take!(c)).ok().map(|x| *x),
+      Some(1)
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "bumpalo")]
+  fn loan_bumpalo() {
+    use bumpalo::{boxed::Box as BumpBox, Bump};
+    let bump = Bump::new();
+    let (a, a_loaned) = LoanedMut::loan(BumpBox::new_in(1, &bump));
+    let (b, b_loaned) = LoanedMut::loan(BumpBox::new_in(2, &bump));
+    let nodes = vec![a_loaned, b_loaned];
+    *a += *b;
+    *b = 0;
+    let nodes = take!(LoanedMut::<Vec<BumpBox<_>>>::from(nodes));
+    assert_eq!(nodes.iter().map(|x| **x).collect::<Vec<_>>(), [3, 0]);
+  }
+
+  #[test]
+  fn list() {
+    use collections::{List, ListBuilder};
+    let mut builder = ListBuilder::new();
+    let borrows = (0..4)
+      .map(|i| builder.push(Box::new(i)))
+      .collect::<Vec<_>>();
+    assert_eq!(builder.len(), 4);
+    for r in borrows {
+      **r *= 2;
+    }
+    let mut list: List<_> = *take!(builder.finish());
+    list.push_front(Box::new(-1));
+    list.iter_mut().for_each(|x| **x += 1);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.pop_front(), Some(Box::new(0)));
+    assert_eq!(format!("{list:?}"), "[1, 3, 5, 7]");
+  }
+
+  #[test]
+  fn unsized_pointees_convert() {
+    use core::fmt::Debug;
+    let (a, la) = LoanedMut::loan(Box::<[u32]>::from([1, 2]));
+    let (b, lb) = LoanedMut::loan(Box::new(3));
+    let lb = unsize!(lb, dyn Debug + Send);
+    let ab = LoanedMut::<(Box<[u32]>, Box<dyn Debug + Send>)>::from((la, lb));
+    let v =
+      LoanedMut::<Vec<(_, _)>>::from(vec![ab, LoanedMut::new((Box::from([]), Box::new(()) as _))]);
+    a[0] = 4;
+    *b = 5;
+    assert_eq!(format!("{:?}", take!(v)), "[([4, 2], 5), ([], ())]");
+  }
+
+  #[test]
+  fn once_lock() {
+    use std::sync::OnceLock;
+    let lock = OnceLock::new();
+    let (r, b) = Loaned::loan(Box::new(AtomicU32::new(1)));
+    assert!(lock.set(b).is_ok());
+    let b = lock
+      .set(Loaned::new(Box::new(AtomicU32::new(2))))
+      .unwrap_err();
+    std::thread::scope(|s| {
+      s.spawn(|| lock.get().unwrap().fetch_add(1, Ordering::Relaxed));
+    });
+    assert_eq!(r.load(Ordering::Relaxed), 2);
+    assert_eq!(take!(lock.into_inner().unwrap()).into_inner(), 2);
+    assert_eq!(take!(b).into_inner(), 2);
+  }
+
+  #[test]
+  fn collect_loans() {
+    let mut refs = Vec::new();
+    let loaned: LoanedMut<Vec<Box<usize>>> = (0..4)
+      .map(|i| {
+        let (r, loaned) = LoanedMut::loan(Box::new(i));
+        refs.push(r);
+        loaned
+      })
+      .collect();
+    for r in refs {
+      *r *= 2;
+    }
+    assert_eq!(take!(loaned), [0, 2, 4, 6].map(Box::new));
+    let loaned: Loaned<Vec<Box<usize>>> = (0..2).map(|i| Loaned::new(Box::new(i))).collect();
+    assert_eq!(take!(loaned), [0, 1].map(Box::new));
+  }
+
+  #[test]
+  fn transpose_result() {
+    let (mut x, mut y, mut z) = (None, None, None);
+    let (a, loaned) = LoanedMut::loan(Box::new(1));
+    let ok = LoanedMut::<Result<Box<_>, &str>>::from(Ok::<_, &str>(loaned));
+    let (b, loaned) = LoanedMut::loan(Box::new(2));
+    let err = LoanedMut::<Result<Box<_>, Box<_>>>::from(Err::<LoanedMut<Box<u8>>, _>(loaned));
+    let nested =
+      LoanedMut::<Option<Option<Box<u8>>>>::from(Some(LoanedMut::from(None::<LoanedMut<_>>)));
+    *a += 10;
+    *b += 10;
+    ok.place(&mut x);
+    err.transpose().unwrap_err().place(&mut y);
+    nested.place(&mut z);
+    assert_eq!(x, Some(Ok(Box::new(11))));
+    assert_eq!(y, Some(Box::new(12)));
+    assert_eq!(z, Some(Some(None)));
+  }
+
+  #[test]
+  fn loan_uninit() {
+    let (a, loaned_a) = LoanedMut::loan_uninit(Box::new_uninit());
+    let (b, loaned_b) = LoanedMut::loan_uninit(Box::new_uninit());
+    let (a, proof_a) = a.write(1u32);
+    let (_, proof_b) = b.write(2);
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+      let loaned = LoanedMut::loan_uninit(Box::new_uninit()).1;
+      let _ = loaned.init(proof_b);
+    }))
+    .is_err());
+    let loaned_a = loaned_a.init(proof_a);
+    *a += 10;
+    assert_eq!(take!(loaned_a), Box::new(11));
+    loaned_b.forget();
+  }
+
+  #[test]
+  fn borrow_keys() {
+    use std::collections::{BTreeSet, HashMap};
+    let (a, loaned) = Loaned::loan(String::from("a"));
+    let mut map = HashMap::new();
+    map.insert(loaned, 1);
+    map.insert(Loaned::new(String::from("b")), 2);
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.get(a), Some(&1));
+    let set = map.into_keys().collect::<BTreeSet<_>>();
+    assert!(set.contains("b"));
+    let name: &str = set.first().unwrap().as_ref();
+    assert_eq!(name, "a");
+    for loaned in set {
+      loaned.forget();
+    }
+  }
+
+  #[test]
+  fn loan_scope() {
+    let mut scope = LoanScope::new();
+    let (a, loaned) = LoanedMut::loan(Box::new(0));
+    scope.push(loaned);
+    let b = scope.loan(Box::new(1));
+    let c = scope.loan_shared(Box::new(2));
+    assert_eq!(scope.len(), 3);
+    *a += *c;
+    *b += *c;
+    drop!(scope);
+  }
+
+  #[cfg(feature = "allocator_api")]
+  #[test]
+  fn custom_allocator() {
+    use core::alloc::{AllocError, Allocator, Layout};
+    use core::ptr::NonNull;
+    use std::alloc::Global;
+
+    #[derive(Clone, Copy)]
+    struct Counting<'a>(&'a AtomicU32);
+
+    unsafe impl Allocator for Counting<'_> {
+      fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        Global.allocate(layout)
+      }
+      unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+        Global.deallocate(ptr, layout)
+      }
+    }
+
+    let count = AtomicU32::new(0);
+    let alloc = Counting(&count);
+    let mut nodes = Vec::new_in(alloc);
+    let (a, loaned) = LoanedMut::loan(Box::new_in(1, alloc));
+    nodes.push(loaned);
+    nodes.push(LoanedMut::new(Box::new_in(2, alloc)));
+    let nodes = LoanedMut::<Vec<Box<_, _>, _>>::from(nodes);
+    *a += 10;
+    let nodes = take!(nodes);
+    assert_eq!(*nodes[0], 11);
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+    drop(nodes);
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+  }
+
+  #[test]
+  fn index_loaned() {
+    let (a, vec) = Loaned::loan(vec![1, 2, 3]);
+    let (b, slice) = Loaned::loan(Box::<[u32]>::from([4, 5]));
+    assert_eq!(vec[1], 2);
+    assert_eq!(vec.get(3), None);
+    assert_eq!(slice[0], 4);
+    assert_eq!(slice.get(1..), Some(&[5][..]));
+    assert_eq!((a, b), (&[1, 2, 3][..], &[4, 5][..]));
+    assert_eq!(take!(vec), [1, 2, 3]);
+    assert_eq!(*take!(slice), [4, 5]);
+  }
+
+  #[test]
+  fn vec_conversion_layout() {
+    let mut vec = Vec::with_capacity(8);
+    let (a, loaned) = LoanedMut::loan(Box::new(0));
+    vec.push(loaned);
+    let ptr = vec.as_ptr() as usize;
+    let loaned = LoanedMut::<Vec<Box<_>>>::from(vec);
+    *a = 1;
+    let vec = take!(loaned);
+    assert_eq!(
+      (vec.as_ptr() as usize, vec.len(), vec.capacity()),
+      (ptr, 1, 8)
+    );
+
+    let zsts = vec![Loaned::new(()), Loaned::new(()), Loaned::new(())];
+    let zsts = take!(Loaned::<Vec<()>>::from(zsts));
+    assert_eq!(zsts.len(), 3);
+    let zsts = Loaned::<Vec<()>>::from(Vec::<Loaned<()>>::new());
+    assert!(take!(zsts).is_empty());
+  }
+
+  #[test]
+  fn loan_ext() {
+    use prelude::*;
+    let (a, loaned_a) = Box::new(1).loan_mut();
+    let (b, loaned_b) = std::sync::Arc::new(2).loan();
+    let mut list = vec![loaned_a];
+    let (c, loaned_c) = Box::new(3).loan_mut();
+    list.push(loaned_c);
+    *a += *b;
+    *c += *b;
+    assert_eq!(
+      take!(LoanedMut::<Vec<Box<_>>>::from(list)),
+      [Box::new(3), Box::new(5)]
+    );
+    assert_eq!(*take!(loaned_b), 2);
+  }
+
+  #[test]
+  #[cfg(feature = "debug-loans")]
+  #[should_panic = "overlapping places"]
+  fn merge_overlapping_places() {
+    let a = LoanedMut::new(Box::new(1));
+    let b = LoanedMut::new(Box::new(2));
+    let ab = LoanedMut::merge((Box::new(0), Box::new(0)), |ab, m| {
+      let p = &mut ab.0 as *mut _;
+      m.place(a, unsafe { &mut *p });
+      m.place(b, unsafe { &mut *p });
+    });
+    drop!(ab);
+  }
+
+  #[test]
+  fn par_build() {
+    let (borrows, scope) = par::build(8, |i, scope| {
+      (0..i)
+        .map(|j| scope.loan(Box::new(i * 10 + j)))
+        .collect::<Vec<_>>()
+    });
+    for borrow in borrows.into_iter().flatten() {
+      *borrow += 100;
+    }
+    let values = take!(scope);
+    assert_eq!(values.len(), 28);
+    assert!(values.windows(2).all(|w| w[0] < w[1]));
+    assert!(values.iter().all(|x| **x >= 100));
+  }
+
+  #[test]
+  fn wrap_loans() {
+    let (a, loaned) = LoanedMut::loan(Box::new(1));
+    let pinned: LoanedMut<Pin<Box<u32>>> = loaned.wrap();
+    *a += 1;
+    let loaned: LoanedMut<Box<u32>> = pinned.unwrap_inner();
+    assert_eq!(take!(loaned), Box::new(2));
+    let (b, loaned) = Loaned::loan(Box::new(3));
+    let wrapped: Loaned<ManuallyDrop<Box<u32>>> = loaned.wrap();
+    assert_eq!(***wrapped, *b);
+    assert_eq!(take!(wrapped.unwrap_inner()), Box::new(3));
+  }
+
+  #[test]
+  #[cfg(feature = "stats")]
+  fn stats_leaks() {
+    let before = stats::snapshot();
+    let loaned = LoanedMut::loan(Box::new(1)).1.with_policy::<policy::Leak>();
+    drop(loaned);
+    let (a, loaned) = LoanedMut::loan(Box::new(2));
+    assert_eq!(loaned.try_take(a).ok(), Some(Box::new(2)));
+    let diff = stats::snapshot().since(before);
+    assert!(diff.loans >= 2);
+    assert!(diff.leaks >= 1);
+    assert!(diff.takes >= 1);
+  }
+
+  #[test]
+  fn extend_loans() {
+    let mut vec = LoanedVec::new();
+    let mut borrows = Vec::new();
+    for batch in 0..3 {
+      let (b, l): (Vec<_>, Vec<_>) = (0..2)
+        .map(|i| LoanedMut::loan(Box::new(batch * 2 + i)))
+        .unzip();
+      borrows.extend(b);
+      vec.extend(l);
+    }
+    vec.extend([Loaned::new(Box::new(6))]);
+    let mut existing = vec![Box::new(-1)];
+    let mut place = VecPlace::new(&mut existing);
+    let (c, loaned) = LoanedMut::loan(Box::new(0));
+    place.extend([loaned, LoanedMut::new(Box::new(8))]);
+    let loaned = LoanedMut::<Vec<_>>::from(vec);
+    for borrow in borrows {
+      *borrow += 10;
+    }
+    *c = 7;
+    assert_eq!(take!(loaned), [10, 11, 12, 13, 14, 15, 6].map(Box::new));
+    assert_eq!(existing, [-1, 7, 8].map(Box::new));
+  }
+
+  #[test]
+  fn boxed_conversions() {
+    let (a, loaned) = LoanedMut::loan(Box::new(1));
+    let slice: Box<[_]> = Box::new([loaned, LoanedMut::new(Box::new(2))]);
+    let slice = LoanedMut::<Box<[Box<_>]>>::from(slice);
+    let (s, string) = LoanedMut::loan(String::from("ab"));
+    let string = string.into_boxed_str().unwrap();
+    let mut spare = String::with_capacity(4);
+    spare.push('c');
+    let spare = LoanedMut::new(spare).into_boxed_str().unwrap_err();
+    *a = 0;
+    s.make_ascii_uppercase();
+    let string = LoanedMut::<String>::from(string);
+    s.make_ascii_lowercase();
+    let string = string.into_boxed_str().unwrap();
+    s[1..].make_ascii_uppercase();
+    assert_eq!(take!(slice), Box::from([Box::new(0), Box::new(2)]));
+    assert_eq!(take!(string), Box::from("aB"));
+    assert_eq!(take!(spare), "c");
+  }
+
+  #[test]
+  #[cfg(feature = "smallvec")]
+  fn smallvec_conversion() {
+    use smallvec::SmallVec;
+    let (a, loaned) = LoanedMut::loan(Box::new(1));
+    let inline: SmallVec<[_; 2]> = SmallVec::from_iter([loaned]);
+    let inline = LoanedMut::<SmallVec<[Box<_>; 2]>>::from(inline);
+    let spilled = (0..3).map(|i| LoanedMut::new(Box::new(i)));
+    let spilled = LoanedMut::<SmallVec<[Box<_>; 2]>>::from(spilled.collect::<SmallVec<[_; 2]>>());
+    *a = 5;
+    assert_eq!(*take!(inline), [Box::new(5)]);
+    let spilled = take!(spilled);
+    assert!(spilled.spilled());
+    assert_eq!(*spilled, [0, 1, 2].map(Box::new));
+  }
+
+  #[test]
+  fn drop_all_loans() {
+    use std::rc::Rc;
+    let counter = Rc::new(());
+    let (a, loaned) = LoanedMut::loan(Box::new(counter.clone()));
+    let handle = LoanHandle::new(LoanedMut::new(Box::new(counter.clone())));
+    let loans = vec![loaned, LoanedMut::new(Box::new(counter.clone()))];
+    assert_eq!(Rc::strong_count(&counter), 4);
+    *a = Rc::new(());
+    drop_all!(loans);
+    drop_all!([handle]);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    struct Node<'t>(Option<&'t Node<'t>>);
+    let (a, a_loaned) = Loaned::loan(Box::new(Node(None)));
+    let (b, b_loaned) = Loaned::loan(Box::new(Node(Some(a))));
+    assert!(b.0.is_some_and(|a| a.0.is_none()));
+    drop_all!([a_loaned, b_loaned]);
+  }
+
+  #[test]
+  fn boxed_slice_borrow() {
+    let (slice, loaned) = LoanedMut::loan(Box::from([1, 2, 3]));
+    let vec = LoanedMut::<Vec<_>>::from(loaned);
+    slice[0] = 4;
+    let boxed = vec.into_boxed_slice().unwrap();
+    slice[1] = 5;
+    let vec = LoanedMut::<Vec<_>>::from(boxed);
+    slice[2] = 6;
+    assert_eq!(take!(vec), [4, 5, 6]);
+    let zsts = LoanedMut::new(Vec::<()>::with_capacity(4));
+    assert_eq!(take!(zsts.into_boxed_slice().unwrap()).len(), 0);
+  }
+
+  #[test]
+  #[cfg(feature = "debug-loans")]
+  fn loan_origin_released() {
+    let (a, loaned) = LoanedMut::loan(Box::new(1));
+    assert_eq!(loan_origin(a).unwrap().line(), line!() - 1);
+    *a = 2;
+    let a = take!(loaned);
+    assert!(loan_origin(&*a).is_none());
+
+    let (b, b_loaned) = LoanedMut::loan(Box::new(1));
+    let (c, c_loaned) = LoanedMut::loan(Box::new(2));
+    let vec = LoanedMut::<Vec<Box<_>>>::from(vec![b_loaned, c_loaned]);
+    assert!(loan_origin(b).is_some() && loan_origin(c).is_some());
+    *b += *c;
+    let vec = take!(vec);
+    assert!(vec.iter().all(|x| loan_origin(&**x).is_none()));
+
+    let (d, loaned) = LoanedMut::loan(Box::new(3));
+    let d = loaned.try_take(d).unwrap();
+    assert!(loan_origin(&*d).is_none());
+  }
+
+  #[test]
+  fn share_then_resume() {
+    let (borrow, loaned) = LoanedMut::loan(Box::new(vec![1, 2]));
+    borrow.push(3);
+    let (sum, borrow, loaned) = loaned.share(borrow, |v| {
+      let (a, b) = (&v[..1], &v[1..]);
+      a.iter().chain(b).sum::<i32>()
+    });
+    borrow.push(sum);
+    let (len, borrow, loaned) = loaned.share(borrow, |v| v.len());
+    borrow[0] = 0;
+    assert_eq!(len, 4);
+    assert_eq!(*take!(loaned), [0, 2, 3, 6]);
+  }
+
+  #[test]
+  fn freeze_then_read() {
+    let (borrow, loaned) = LoanedMut::loan(Box::new(vec![1]));
+    borrow.push(2);
+    let (frozen, loaned) = loaned.freeze(borrow);
+    assert_eq!(*frozen, [1, 2]);
+    let other = loaned.borrow();
+    assert_eq!(frozen.len() + other.len(), 4);
+    assert_eq!(*take!(loaned), [1, 2]);
+  }
+
+  #[test]
+  fn shorten_then_write() {
+    fn finish<'t>(borrow: &'t mut [u32; 3], loaned: LoanedMut<'t, Box<[u32; 3]>>) -> [u32; 3] {
+      borrow[1] = 2;
+      let (borrow, loaned) = loaned.shorten(borrow);
+      borrow[2] = 3;
+      *take!(loaned)
+    }
+    let (borrow, loaned) = LoanedMut::loan(Box::new([0; 3]));
+    borrow[0] = 1;
+    assert_eq!(finish(borrow, loaned), [1, 2, 3]);
+  }
+
+  #[test]
+  fn merge_loan_wrappers() {
+    struct Tagged<L>(&'static str, L);
+    impl<'t, L: LoanWrapper<'t>> LoanWrapper<'t> for Tagged<L> {
+      type Value = L::Value;
+      fn into_loan(self) -> LoanedMut<'t, L::Value> {
+        self.1.into_loan()
+      }
+    }
+    impl<'t, L: SharedLoanWrapper<'t>> SharedLoanWrapper<'t> for Tagged<L> {
+      fn into_shared_loan(self) -> Loaned<'t, L::Value> {
+        self.1.into_shared_loan()
+      }
+    }
+
+    let (a, a_loaned) = Loaned::loan(Box::new(1));
+    let (b, b_loaned) = Loaned::loan(Box::new(2));
+    let (a_loaned, b_loaned) = (Tagged("a", a_loaned), Tagged("b", b_loaned));
+    assert_eq!((a_loaned.0, b_loaned.0), ("a", "b"));
+    let pair = Loaned::merge((Box::new(0), Box::new(0)), |pair, m| {
+      m.place(a_loaned, &mut pair.0);
+      m.place_no_drop(b_loaned, &mut pair.1);
+    });
+    assert_eq!(*a + *b, 3);
+    assert_eq!(take!(pair), (Box::new(1), Box::new(2)));
+
+    let (c, c_loaned) = LoanedMut::loan(Box::new(0));
+    let single = LoanedMut::merge(Box::new(0), |x, m| {
+      m.place_no_drop(Tagged("c", c_loaned), x)
+    });
+    *c = 3;
+    assert_eq!(take!(single), Box::new(3));
+  }
 }
@@ -0,0 +1,88 @@
+use crate::*;
+use core::cell::Cell;
+
+/// A single-threaded cell which a [`LoanedMut`] can be placed into exactly
+/// once, while the loaned borrow is still in use.
+///
+/// Placing borrows the cell for `'t`, so the value can only be retrieved (with
+/// [`LoanCell::into_inner`] or [`LoanCell::take`]) once `'t` has expired. See
+/// [`LoanedCell`] for a thread-safe equivalent.
+///
+/// # Example
+/// ```
+/// use loaned::{LoanCell, LoanedMut};
+/// let cell = LoanCell::new();
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(0));
+/// cell.set(loaned).unwrap();
+/// let rejected = cell.set(LoanedMut::new(Box::new(1))).unwrap_err();
+/// *borrow = 123;
+/// loaned::drop!(rejected);
+/// assert_eq!(cell.into_inner(), Some(Box::new(123)));
+/// ```
+pub struct LoanCell<T> {
+  value: Cell<Option<RawLoaned<T>>>,
+}
+
+impl<T> LoanCell<T> {
+  /// Constructs a new, empty `LoanCell`.
+  #[inline]
+  pub const fn new() -> Self {
+    LoanCell {
+      value: Cell::new(None),
+    }
+  }
+
+  /// Places a loaned value into the cell. If the cell already contains a
+  /// value, the loaned value is returned instead.
+  pub fn set<'t>(&'t self, loaned: LoanedMut<'t, T>) -> Result<(), LoanedMut<'t, T>> {
+    match self.value.take() {
+      Some(value) => {
+        self.value.set(Some(value));
+        Err(loaned)
+      }
+      None => {
+        self.value.set(Some(loaned.into_raw()));
+        Ok(())
+      }
+    }
+  }
+
+  /// Returns `true` if a value has been placed into the cell.
+  pub fn is_set(&self) -> bool {
+    let value = self.value.take();
+    let is_set = value.is_some();
+    self.value.set(value);
+    is_set
+  }
+
+  /// Takes the value out of the cell, if one has been placed.
+  pub fn take(&mut self) -> Option<T> {
+    let value = self.value.get_mut().take()?;
+    Some(unsafe { MaybeUninit::from(value).assume_init() })
+  }
+
+  /// Consumes the cell, returning the value, if one has been placed.
+  #[inline]
+  pub fn into_inner(mut self) -> Option<T> {
+    self.take()
+  }
+}
+
+impl<T> Default for LoanCell<T> {
+  fn default() -> Self {
+    LoanCell::new()
+  }
+}
+
+impl<T> core::fmt::Debug for LoanCell<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "LoanCell(..)")
+  }
+}
+
+impl<T> Drop for LoanCell<T> {
+  fn drop(&mut self) {
+    // Placing borrows the cell for `'t`, so `'t` must have expired by now.
+    self.take();
+  }
+}
@@ -0,0 +1,114 @@
+use crate::*;
+use core::fmt::Debug;
+
+/// Loans values on demand, collecting the loaned halves so that they don't
+/// need to be stored separately.
+///
+/// Once `'t` has expired, the values can be retrieved as a `Vec` with
+/// [`take!`], or dropped with [`drop!`]. Dropping a `LoanScope` without either
+/// behaves like dropping a [`LoanedMut`]. For values of different types, see
+/// [`DropQueue`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanScope};
+/// let mut scope = LoanScope::new();
+/// let a = scope.loan(Box::new(1));
+/// let b = scope.loan_shared(Box::new(2));
+/// *a += *b;
+/// assert_eq!(take!(scope), [Box::new(3), Box::new(2)]);
+/// ```
+#[must_use = "dropping a `LoanScope` panics; use `loaned::drop!` instead"]
+pub struct LoanScope<'t, T>(LoanedVec<'t, T>);
+
+impl<'t, T> LoanScope<'t, T> {
+  /// Constructs a new, empty `LoanScope`.
+  #[inline]
+  pub const fn new() -> Self {
+    LoanScope(LoanedVec::new())
+  }
+
+  /// Mutably loans a value, returning the borrow.
+  pub fn loan(&mut self, value: T) -> &'t mut T::Target
+  where
+    T: Loanable<'t> + DerefMut,
+  {
+    let (borrow, loaned) = LoanedMut::loan(value);
+    self.0.push(loaned);
+    borrow
+  }
+
+  /// Immutably loans a value, returning the borrow.
+  pub fn loan_shared(&mut self, value: T) -> &'t T::Target
+  where
+    T: Loanable<'t>,
+  {
+    let (borrow, loaned) = Loaned::loan(value);
+    self.0.push(loaned.into());
+    borrow
+  }
+
+  /// Adds an already-loaned value to the scope.
+  #[inline]
+  pub fn push(&mut self, loaned: impl Into<LoanedMut<'t, T>>) {
+    self.0.push(loaned.into())
+  }
+
+  /// Moves all of the values of `other` into this scope, leaving `other`
+  /// empty.
+  #[inline]
+  pub fn append(&mut self, other: &mut Self) {
+    self.0.append(&mut other.0)
+  }
+
+  /// Returns the number of values in the scope.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if the scope has no values.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Converts the scope into a loaned `Vec` of its values, in the order they
+  /// were loaned.
+  #[inline]
+  pub fn into_inner(self) -> LoanedMut<'t, Vec<T>> {
+    self.0.into()
+  }
+}
+
+impl<'t, T> Default for LoanScope<'t, T> {
+  fn default() -> Self {
+    LoanScope::new()
+  }
+}
+
+impl<'t, T> Debug for LoanScope<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "LoanScope(..)")
+  }
+}
+
+impl<'t, T> Extend<LoanedMut<'t, T>> for LoanScope<'t, T> {
+  fn extend<I: IntoIterator<Item = LoanedMut<'t, T>>>(&mut self, iter: I) {
+    self.0.extend(iter)
+  }
+}
+
+impl<'t, T> Extend<Loaned<'t, T>> for LoanScope<'t, T> {
+  fn extend<I: IntoIterator<Item = Loaned<'t, T>>>(&mut self, iter: I) {
+    self.0.extend(iter)
+  }
+}
+
+impl<'t, T> LoanWrapper<'t> for LoanScope<'t, T> {
+  type Value = Vec<T>;
+  #[inline]
+  fn into_loan(self) -> LoanedMut<'t, Vec<T>> {
+    self.into_inner()
+  }
+}
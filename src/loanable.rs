@@ -11,22 +11,142 @@ use crate::*;
 ///
 /// In particular, this can't be implemented for types like `Cow`, as it may
 /// return a reference to data within `self` (which would be invalidated when
-/// `self` is moved).
+/// `self` is moved). Use [`Loaned::loan_cow`] to loan the owned form of a
+/// `Cow`, or [`OwnedCow`] to loan a `Cow` known to be owned, instead.
 ///
 /// This is closely related to whether the type can unconditionally implement
 /// [`Unpin`] (i.e. even when `Self::Target: !Unpin`).
+///
+/// To loan a [`Pin`] while preserving the pinning guarantee, use
+/// [`Loaned::loan_pin`] or [`LoanedMut::loan_pin`].
+///
+/// This is implemented for `Box<T>`, `Rc<T>`, and `Arc<T>` for unsized `T`
+/// (e.g. `Box<str>` or `Arc<[T]>`), as well as for the guards of `RefCell`,
+/// `Mutex`, and `RwLock` (which deref into the lock, not the guard). With the
+/// `triomphe` feature, it is also implemented for `triomphe::Arc`, and with
+/// the `bumpalo` feature, for `bumpalo::boxed::Box` (allowing bump-allocated
+/// values to be loaned for no longer than their arena lives). With the
+/// `allocator_api` feature (which requires nightly), `Box` and `Vec` may use
+/// any allocator.
+///
+/// It can't be implemented for small-box types like `smallbox::SmallBox`, as
+/// they may store the value inline.
+///
+/// With the `stable_deref` feature enabled, other types implementing
+/// [`StableDeref`] can be loaned by wrapping them in `StableLoanable`.
+///
+/// [`StableDeref`]: https://docs.rs/stable_deref_trait/latest/stable_deref_trait/trait.StableDeref.html
 pub unsafe trait Loanable<'t>: Deref {}
 
+/// Wraps a type implementing [`StableDeref`] so that it can be loaned. Requires
+/// the `stable_deref` feature.
+///
+/// [`StableDeref`]: stable_deref_trait::StableDeref
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, StableLoanable};
+/// use std::ops::{Deref, DerefMut};
+///
+/// struct Handle(Box<u32>);
+///
+/// impl Deref for Handle {
+///   type Target = u32;
+///   fn deref(&self) -> &u32 {
+///     &self.0
+///   }
+/// }
+///
+/// impl DerefMut for Handle {
+///   fn deref_mut(&mut self) -> &mut u32 {
+///     &mut self.0
+///   }
+/// }
+///
+/// unsafe impl stable_deref_trait::StableDeref for Handle {}
+///
+/// let (r, loaned) = LoanedMut::loan(StableLoanable(Handle(Box::new(1))));
+/// *r = 2;
+/// assert_eq!(*take!(loaned).0 .0, 2);
+/// ```
+#[cfg(feature = "stable_deref")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct StableLoanable<T>(pub T);
+
+#[cfg(feature = "stable_deref")]
+impl<T: Deref> Deref for StableLoanable<T> {
+  type Target = T::Target;
+  #[inline(always)]
+  fn deref(&self) -> &T::Target {
+    &self.0
+  }
+}
+
+#[cfg(feature = "stable_deref")]
+impl<T: DerefMut> DerefMut for StableLoanable<T> {
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut T::Target {
+    &mut self.0
+  }
+}
+
+// `T: 't` ensures that borrows like `&'a U` can't be loaned for longer than `'a`.
+#[cfg(feature = "stable_deref")]
+unsafe impl<'t, T: stable_deref_trait::StableDeref + 't> Loanable<'t> for StableLoanable<T> {}
+
 #[cfg(feature = "alloc")]
 mod _alloc {
   use crate::*;
+  #[cfg(not(feature = "allocator_api"))]
   unsafe impl<'t, T: ?Sized> Loanable<'t> for alloc::boxed::Box<T> {}
+  #[cfg(not(feature = "allocator_api"))]
   unsafe impl<'t, T> Loanable<'t> for alloc::vec::Vec<T> {}
+  // The allocator must outlive the loan, as dropping it could free the pointee.
+  #[cfg(feature = "allocator_api")]
+  unsafe impl<'t, T: ?Sized, A: core::alloc::Allocator + 't> Loanable<'t>
+    for alloc::boxed::Box<T, A>
+  {
+  }
+  #[cfg(feature = "allocator_api")]
+  unsafe impl<'t, T, A: core::alloc::Allocator + 't> Loanable<'t> for alloc::vec::Vec<T, A> {}
   unsafe impl<'t> Loanable<'t> for alloc::string::String {}
   unsafe impl<'t, T: ?Sized> Loanable<'t> for alloc::rc::Rc<T> {}
   unsafe impl<'t, T: ?Sized> Loanable<'t> for alloc::sync::Arc<T> {}
 }
 
+#[cfg(feature = "std")]
+mod _std {
+  use crate::*;
+  use core::cell::{Ref, RefMut};
+  use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+  unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for Ref<'b, T> {}
+  unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for RefMut<'b, T> {}
+  unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for MutexGuard<'b, T> {}
+  unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for RwLockReadGuard<'b, T> {}
+  unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for RwLockWriteGuard<'b, T> {}
+}
+
+#[cfg(feature = "triomphe")]
+unsafe impl<'t, T: ?Sized> Loanable<'t> for triomphe::Arc<T> {}
+
+// The arena must outlive the loan, like with references.
+#[cfg(feature = "bumpalo")]
+unsafe impl<'t, 'b: 't, T: ?Sized> Loanable<'t> for bumpalo::boxed::Box<'b, T> {}
+
+unsafe impl<'t, P: Loanable<'t>> Loanable<'t> for Pin<P> {}
+
 // The usefulness of this implementation is dubious at best, but it's here for completeness.
 unsafe impl<'t, 'a: 't, T: ?Sized> Loanable<'t> for &'a T {}
 unsafe impl<'t, 'a: 't, T: ?Sized> Loanable<'t> for &'a mut T {}
+
+#[cfg(feature = "allocator_api")]
+crate::compile_fail_test!(allocator_outlives_loan: "E0515" {
+  #![feature(allocator_api)]
+  fn dangling() -> &'static mut u64 {
+    let alloc = std::alloc::System;
+    let (borrow, loaned) = loaned::LoanedMut::loan(Box::new_in(7u64, &alloc));
+    loaned.forget();
+    borrow
+  }
+});
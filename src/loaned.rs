@@ -9,7 +9,9 @@ use core::{
 /// may hold an `&'t` reference to such allocations).
 ///
 /// Thus, for the duration of `'t`, one cannot mutably access this value.
-/// However, unlike [`LoanedMut`], one can immutably access it.
+/// However, unlike [`LoanedMut`], one can immutably access it; `Loaned<'t, T>`
+/// dereferences to `T`, so e.g. a `Loaned<'t, Vec<U>>` can be indexed directly
+/// with `loaned[i]` or `loaned.get(i)`.
 ///
 /// One can store this value somewhere with `Loaned::place`, which will ensure
 /// that it cannot be used for the duration of `'t`.
@@ -23,17 +25,28 @@ use core::{
 /// there is no way in the type system to enforce this, nor any way to check
 /// this at runtime, dropping a `Loaned` panics.
 ///
-/// If leaking is intentional, use a `ManuallyDrop<Loaned<'t, T>>`.
+/// If leaking is intentional, use a `ManuallyDrop<Loaned<'t, T>>` (e.g. via the
+/// [`leak!`] macro), or [`Loaned::forget`].
+/// To leak the value whenever it is dropped (e.g. for a loan embedded in a
+/// public type), use a different [drop policy](policy), as in
+/// `Loaned<'t, T, policy::Leak>`.
 ///
 /// To drop the inner value, use the [`drop!`] macro, which will statically
 /// ensure that `'t` has expired.
+///
+/// # Layout
+///
+/// `Loaned<'t, T, P>` is guaranteed to be `#[repr(transparent)]` over
+/// [`RawLoaned<T>`](raw::RawLoaned), which has the same size and alignment as
+/// `T`. To pass a loaned `Box` across an FFI boundary, see [`LoanHandle`].
 #[must_use = "dropping a `Loaned` panics; use `loaned::drop!` instead"]
 #[repr(transparent)]
-pub struct Loaned<'t, T> {
+pub struct Loaned<'t, T, P: DropPolicy = Panic> {
   /// Invariant: the target of `inner` is borrowed for `'t`, so it may only be
   /// accessed immutably (not mutably or uniquely) for the duration of `'t`.
   pub(crate) inner: RawLoaned<T>,
   pub(crate) _contravariant: PhantomData<fn(&'t ())>,
+  pub(crate) _policy: PhantomData<fn() -> P>,
 }
 
 /// Like `&T`, `Loaned<T>` is only `Send` if `T` is `Sync`.
@@ -56,25 +69,51 @@ pub struct Loaned<'t, T> {
 /// loaned::drop!(x);
 /// ```
 ///
+/// Unlike `&T`, it also owns the `T`, which may be taken (and dropped) on the
+/// other thread, so `T` must also be `Send`. Otherwise, e.g. a `MutexGuard`
+/// could be unlocked on a different thread than it was locked on:
+///
+/// ```rust,compile_fail E0277
+/// use std::sync::Mutex;
+/// static MUTEX: Mutex<i32> = Mutex::new(1);
+/// let x = loaned::Loaned::new(MUTEX.lock().unwrap());
+/// std::thread::spawn(move || {
+///   // SAFETY: nothing was loaned out from `x`.
+///   drop(unsafe { x.take_unchecked() }); // <- unlocks on another thread
+/// });
+/// ```
+///
 /// If you need to safely send this value, you can convert it to a `LoanedMut<'t, T>` with `Into`.
-unsafe impl<'t, T: Sync> Send for Loaned<'t, T> {}
+unsafe impl<'t, T: Send + Sync, P: DropPolicy> Send for Loaned<'t, T, P> {}
 
 impl<'t, T> Loaned<'t, T> {
   /// Constructs a `Loaned` from a given smart pointer, returning the borrow
   /// along with the loaned pointer.
   #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
   pub fn loan(value: T) -> (&'t T::Target, Self)
   where
     T: Loanable<'t>,
   {
     let loaned = unsafe { Loaned::from_raw(RawLoaned::new(value)) };
-    (loaned.borrow(), loaned)
+    let borrow = loaned.borrow();
+    record_loan(borrow);
+    (borrow, loaned)
   }
 
   /// Creates a `Loaned` without actually loaning it. If you want to loan it,
   /// use [`Loaned::loan`] or [`Loaned::borrow`].
+  ///
+  /// This can be used in const contexts, e.g. to build a loaned structure in a
+  /// `static`:
+  /// ```
+  /// use loaned::Loaned;
+  /// static TABLE: Loaned<[&[u32]; 2]> =
+  ///   Loaned::from_array([Loaned::new(&[1, 2]), Loaned::new(&[3])]);
+  /// assert_eq!(TABLE[1], [3]);
+  /// ```
   #[inline(always)]
-  pub fn new(value: T) -> Self {
+  pub const fn new(value: T) -> Self {
     unsafe { Loaned::from_raw(RawLoaned::new(value)) }
   }
 
@@ -85,8 +124,23 @@ impl<'t, T> Loaned<'t, T> {
     Place::place(self.into(), place)
   }
 
+  /// Like [`Loaned::place`] into a `T`, but returns the previous value instead
+  /// of dropping it. See [`LoanedMut::swap_place`].
+  #[inline]
+  pub fn swap_place(self, place: &'t mut T) -> T {
+    LoanedMut::from(self).swap_place(place)
+  }
+
+  /// Like [`Loaned::place`] into a `T`, but overwrites the previous value
+  /// without dropping it. See [`LoanedMut::place_no_drop`].
+  #[inline]
+  pub fn place_no_drop(self, place: &'t mut T) {
+    LoanedMut::from(self).place_no_drop(place)
+  }
+
   /// Borrows the pointee of the value, returning a reference valid for `'t`.
   #[inline(always)]
+  #[allow(clippy::should_implement_trait)]
   pub fn borrow(&self) -> &'t T::Target
   where
     T: Loanable<'t>,
@@ -94,21 +148,478 @@ impl<'t, T> Loaned<'t, T> {
     unsafe { &*(&**self.inner.as_ref() as *const _) }
   }
 
+  /// Returns a pointer to the pointee of the value, e.g. for identity
+  /// comparisons or as a key in a pointer-keyed map.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (borrow, loaned) = Loaned::loan(Box::new(1));
+  /// assert!(std::ptr::eq(loaned.as_ptr(), borrow));
+  /// assert_eq!(take!(loaned), Box::new(1));
+  /// ```
+  #[inline(always)]
+  pub fn as_ptr(&self) -> *const T::Target
+  where
+    T: Deref,
+  {
+    &***self
+  }
+
+  /// Borrows a part of the pointee of the value (e.g. one of its fields),
+  /// returning a reference valid for `'t`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (_, loaned) = Loaned::loan(Box::new((1, String::from("abc"))));
+  /// let s: &str = loaned.borrow_map(|x| &x.1[1..]);
+  /// assert_eq!(s, "bc");
+  /// assert_eq!(take!(loaned).0, 1);
+  /// ```
+  #[inline]
+  pub fn borrow_map<U: ?Sized>(&self, f: impl FnOnce(&'t T::Target) -> &'t U) -> &'t U
+  where
+    T: Loanable<'t>,
+    T::Target: 't,
+  {
+    f(self.borrow())
+  }
+
+  /// Immutably borrows the contents of a loaned `RefCell` (e.g. a loaned
+  /// `Rc<RefCell<U>>`), returning a guard valid for `'t`, like
+  /// [`RefCell::borrow`].
+  ///
+  /// The cell itself can be borrowed for `'t` with [`Loaned::borrow`] and
+  /// mutated through, like any `&RefCell<U>`.
+  ///
+  /// # Panics
+  /// Panics if the value is currently mutably borrowed.
+  ///
+  /// [`RefCell::borrow`]: core::cell::RefCell::borrow
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedRcCell, Loaned};
+  /// use std::{cell::RefCell, rc::Rc};
+  /// let (cell, loaned): (_, LoanedRcCell<_>) = Loaned::loan(Rc::new(RefCell::new(vec![1])));
+  /// cell.borrow_mut().push(2);
+  /// assert_eq!(*loaned.borrow_cell(), [1, 2]);
+  /// loaned.borrow_cell_mut().push(3);
+  /// assert_eq!(Rc::into_inner(take!(loaned)).unwrap().into_inner(), [1, 2, 3]);
+  /// ```
+  #[inline]
+  #[track_caller]
+  pub fn borrow_cell<U: ?Sized>(&self) -> core::cell::Ref<'t, U>
+  where
+    T: Loanable<'t, Target = core::cell::RefCell<U>>,
+  {
+    self.borrow().borrow()
+  }
+
+  /// Mutably borrows the contents of a loaned `RefCell`, returning a guard
+  /// valid for `'t`, like [`RefCell::borrow_mut`]. See
+  /// [`Loaned::borrow_cell`].
+  ///
+  /// # Panics
+  /// Panics if the value is currently borrowed.
+  ///
+  /// [`RefCell::borrow_mut`]: core::cell::RefCell::borrow_mut
+  #[inline]
+  #[track_caller]
+  pub fn borrow_cell_mut<U: ?Sized>(&self) -> core::cell::RefMut<'t, U>
+  where
+    T: Loanable<'t, Target = core::cell::RefCell<U>>,
+  {
+    self.borrow().borrow_mut()
+  }
+
+  /// Like [`Loaned::borrow_map`], but for parts of the pointee which may not
+  /// exist (e.g. one variant of an enum).
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// enum Node {
+  ///   Leaf(u32),
+  ///   Branch(Box<Node>, Box<Node>),
+  /// }
+  /// let node = Box::new(Node::Branch(Box::new(Node::Leaf(1)), Box::new(Node::Leaf(2))));
+  /// let (_, loaned) = Loaned::loan(node);
+  /// let left = loaned.filter_map_borrow(|node| match node {
+  ///   Node::Branch(left, _) => Some(&**left),
+  ///   Node::Leaf(_) => None,
+  /// });
+  /// assert!(matches!(left, Some(Node::Leaf(1))));
+  /// loaned::drop!(loaned);
+  /// ```
+  #[inline]
+  pub fn filter_map_borrow<U: ?Sized>(
+    &self,
+    f: impl FnOnce(&'t T::Target) -> Option<&'t U>,
+  ) -> Option<&'t U>
+  where
+    T: Loanable<'t>,
+    T::Target: 't,
+  {
+    f(self.borrow())
+  }
+
+  /// Borrows the pointee of the value as a `Loaned<'t, &T::Target>`, like
+  /// `Option::as_deref`.
+  ///
+  /// There is no `LoanedMut` equivalent, since the pointee of a `LoanedMut`
+  /// may not be accessed for the duration of `'t`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (_, a) = Loaned::loan(Box::new(123));
+  /// let b = a.as_deref();
+  /// assert_eq!(*b, &123);
+  /// b.forget();
+  /// assert_eq!(take!(a), Box::new(123));
+  /// ```
+  #[inline(always)]
+  pub fn as_deref(&self) -> Loaned<'t, &'t T::Target>
+  where
+    T: Loanable<'t>,
+  {
+    Loaned::new(self.borrow())
+  }
+
+  /// Combines two loans into a loan of a tuple. Equivalent to
+  /// `Loaned::<(T, U)>::from((self, other))`.
+  #[inline]
+  pub fn zip<U>(self, other: Loaned<'t, U>) -> Loaned<'t, (T, U)> {
+    (self, other).into()
+  }
+
+  /// Like [`Loaned::zip`], but also passes the pointees of both values to `f`,
+  /// which can derive borrows from them.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let a = Loaned::new(Box::new(1));
+  /// let b = Loaned::new(vec![2, 3]);
+  /// let (sum, ab) = a.zip_with(b, |a, b| a + b.iter().sum::<i32>());
+  /// assert_eq!(sum, 6);
+  /// assert_eq!(take!(ab), (Box::new(1), vec![2, 3]));
+  /// ```
+  #[inline]
+  pub fn zip_with<U, R>(
+    self,
+    other: Loaned<'t, U>,
+    f: impl FnOnce(&'t T::Target, &'t U::Target) -> R,
+  ) -> (R, Loaned<'t, (T, U)>)
+  where
+    T: Loanable<'t>,
+    U: Loanable<'t>,
+    T::Target: 't,
+    U::Target: 't,
+  {
+    let r = f(self.borrow(), other.borrow());
+    (r, self.zip(other))
+  }
+
+  /// Intentionally leaks the contained value, without panicking.
+  ///
+  /// This is equivalent to `mem::forget`; see also [`leak!`].
   #[inline(always)]
-  pub(crate) fn into_raw(self) -> RawLoaned<T> {
-    unsafe { ptr::read(&ManuallyDrop::new(self).inner) }
+  pub fn forget(self) {
+    mem::forget(self)
   }
 
+  /// Takes the contained value without statically ensuring that `'t` has
+  /// expired. Prefer [`take!`], which is safe.
+  ///
+  /// # Safety
+  ///
+  /// `'t` must have expired; i.e. none of the borrows loaned out from this
+  /// value may be used again.
   #[inline(always)]
-  pub(crate) unsafe fn from_raw(inner: RawLoaned<T>) -> Self {
+  pub unsafe fn take_unchecked(self) -> T {
+    stats::count_take();
+    MaybeUninit::from(self.into_raw()).assume_init()
+  }
+
+  /// Drops the contained value without statically ensuring that `'t` has
+  /// expired. Prefer [`drop!`], which is safe.
+  ///
+  /// # Safety
+  ///
+  /// See [`Loaned::take_unchecked`].
+  #[inline(always)]
+  pub unsafe fn drop_unchecked(self) {
+    mem::drop(self.take_unchecked())
+  }
+
+  /// Mutably borrows the pointee of the value without statically ensuring
+  /// that `'t` has expired, e.g. for containers which track the loan
+  /// externally.
+  ///
+  /// # Safety
+  ///
+  /// None of the borrows loaned out from this value (nor any reference derived
+  /// from them) may be used again, as creating the returned borrow invalidates
+  /// them.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (borrow, mut loaned) = Loaned::loan(Box::new(1));
+  /// assert_eq!(*borrow, 1);
+  /// // `borrow` is never used again
+  /// unsafe { *loaned.borrow_unchecked_mut() = 2 };
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  #[inline(always)]
+  pub unsafe fn borrow_unchecked_mut(&mut self) -> &mut T::Target
+  where
+    T: DerefMut,
+  {
+    self.inner.as_mut()
+  }
+
+  /// Intentionally leaks the contained value, like `Box::leak`, returning a
+  /// borrow of its pointee.
+  ///
+  /// There is no `LoanedMut` equivalent, since a mutable borrow of the pointee
+  /// may already have been loaned out.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::Loaned;
+  /// let (a, loaned) = Loaned::loan(String::from("interned"));
+  /// let b = loaned.leak();
+  /// assert!(std::ptr::eq(a, b));
+  /// ```
+  #[inline]
+  pub fn leak(self) -> &'t T::Target
+  where
+    T: Loanable<'t>,
+  {
+    let borrow = self.borrow();
+    self.forget();
+    borrow
+  }
+
+  /// Converts this into its raw representation, without ending the loan.
+  ///
+  /// See the [`raw`] module for the requirements on the result.
+  #[inline(always)]
+  pub const fn into_raw(self) -> RawLoaned<T> {
+    // `Self` is `repr(transparent)` over `RawLoaned<T>`.
+    let this = ManuallyDrop::new(self);
+    unsafe { ptr::read(&this as *const ManuallyDrop<Self> as *const RawLoaned<T>) }
+  }
+
+  /// Converts a raw representation back into a `Loaned`.
+  ///
+  /// # Safety
+  ///
+  /// `inner` must have come from [`Loaned::into_raw`] (or [`RawLoaned::new`])
+  /// with the same `'t`; otherwise, the target of the value must be able to be
+  /// borrowed immutably for the remainder of `'t`.
+  #[inline(always)]
+  pub const unsafe fn from_raw(inner: RawLoaned<T>) -> Self {
     Loaned {
       inner,
       _contravariant: PhantomData,
+      _policy: PhantomData,
     }
   }
 }
 
-impl<'t, T> Deref for Loaned<'t, T> {
+impl<'t, T, P: DropPolicy> Loaned<'t, T, P> {
+  /// Changes the [drop policy](policy) of this loan.
+  ///
+  /// Most of the API of `Loaned` is only available with the default policy,
+  /// [`Panic`](policy::Panic).
+  #[inline(always)]
+  pub fn with_policy<Q: DropPolicy>(self) -> Loaned<'t, T, Q> {
+    Loaned {
+      inner: unsafe { ptr::read(&ManuallyDrop::new(self).inner) },
+      _contravariant: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+}
+
+impl<'t, T> Loaned<'t, T> {
+  /// Projects the value into one of its fields, preserving the loan. The rest
+  /// of the value is leaked.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the reference returned by `f` does not point within the value
+  /// (e.g. if it points into a `Box`, rather than to the `Box` itself).
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let ((a, b), ab) = Loaned::loan_with((Box::new(1), Box::new(2)), |ab, l| {
+  ///   (l.loan(&ab.0), l.loan(&ab.1))
+  /// });
+  /// let b2 = ab.map(|ab| &ab.1);
+  /// assert_eq!((*a, *b), (1, 2));
+  /// assert_eq!(take!(b2), Box::new(2));
+  /// ```
+  pub fn map<U>(self, f: impl FnOnce(&T) -> &U) -> Loaned<'t, U> {
+    let value = unsafe { self.inner.as_ref() };
+    let start = value as *const T as usize;
+    let field = f(value) as *const U;
+    assert!(
+      start <= field as usize
+        && field as usize + mem::size_of::<U>() <= start + mem::size_of::<T>(),
+      "`Loaned::map` must return a reference to a field of the value"
+    );
+    let field = unsafe { ptr::read(field.cast::<RawLoaned<U>>()) };
+    let _ = self.into_raw();
+    unsafe { Loaned::from_raw(field) }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> Loaned<'t, T> {
+  /// Loans the owned form of a [`Cow`](alloc::borrow::Cow), converting it
+  /// with [`Cow::into_owned`](alloc::borrow::Cow::into_owned) first.
+  ///
+  /// `Cow` itself cannot implement [`Loanable`]: a `Cow::Borrowed` dereferences
+  /// to data outside of the `Cow`, but a `Cow::Owned` dereferences (through
+  /// [`Borrow`](core::borrow::Borrow), which makes no guarantees about
+  /// stability) to data that may live within it, and thus be invalidated when
+  /// the `Cow` is moved. Forcing ownership and loaning the owned value instead
+  /// relies only on `T`'s [`Loanable`] implementation.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// use std::borrow::Cow;
+  /// let (borrow, loaned) = Loaned::loan_cow(Cow::Borrowed("hello"));
+  /// assert_eq!(borrow, "hello");
+  /// assert_eq!(take!(loaned), "hello");
+  /// ```
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_cow<B>(cow: alloc::borrow::Cow<'_, B>) -> (&'t B, Self)
+  where
+    B: ?Sized + alloc::borrow::ToOwned<Owned = T>,
+    T: Loanable<'t, Target = B>,
+  {
+    Loaned::loan(cow.into_owned())
+  }
+}
+
+impl<'t, T> Loaned<'t, T> {
+  /// Iterates over borrows of the pointees of the elements of a loaned
+  /// collection (e.g. a `Vec<Box<U>>`), each valid for `'t`.
+  ///
+  /// There is no `LoanedMut` equivalent, since the pointees of the elements may
+  /// already have been mutably loaned out.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (a, loaned) = Loaned::loan(Box::new(1));
+  /// let loaned = Loaned::<Vec<Box<_>>>::from(vec![loaned, Loaned::new(Box::new(2))]);
+  /// let borrows = loaned.iter_loans().collect::<Vec<&u32>>();
+  /// assert!(std::ptr::eq(a, borrows[0]));
+  /// assert_eq!(borrows, [&1, &2]);
+  /// assert_eq!(take!(loaned), [Box::new(1), Box::new(2)]);
+  /// ```
+  pub fn iter_loans<U>(&self) -> impl Iterator<Item = &'t U::Target> + 't
+  where
+    T: Loanable<'t, Target = [U]>,
+    U: Deref + 't,
+  {
+    self.borrow().iter().map(|x| &**x)
+  }
+}
+
+impl<'t, P: Loanable<'t>> Loaned<'t, Pin<P>> {
+  /// Loans a pinned pointer, returning the pinned borrow along with the loaned
+  /// pointer.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// use std::{marker::PhantomPinned, pin::Pin, rc::Rc};
+  /// let (borrow, loaned) = Loaned::loan_pin(Rc::pin((123, PhantomPinned)));
+  /// let borrow: Pin<&(u32, PhantomPinned)> = borrow;
+  /// assert_eq!(borrow.0, 123);
+  /// assert_eq!(take!(loaned).0, 123);
+  /// ```
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_pin(value: Pin<P>) -> (Pin<&'t P::Target>, Self) {
+    let loaned = unsafe { Loaned::from_raw(RawLoaned::new(value)) };
+    let borrow = unsafe { &*(&**loaned.inner.as_ref() as *const P::Target) };
+    record_loan(borrow);
+    // The pointee remains pinned, as it is only accessible through `Pin<P>`.
+    (unsafe { Pin::new_unchecked(borrow) }, loaned)
+  }
+}
+
+macro_rules! downcast_impls {
+  ($($Any:ty),*) => {$(
+    #[cfg(feature = "alloc")]
+    impl<'t> Loaned<'t, Box<$Any>> {
+      /// Attempts to downcast the box to a concrete type, preserving the loan.
+      ///
+      /// There is no equivalent for `LoanedMut`, as checking the type requires
+      /// accessing the (potentially mutably borrowed) pointee.
+      ///
+      /// # Example
+      /// ```
+      /// use loaned::{take, Loaned};
+      /// use std::any::Any;
+      /// let (borrow, loaned) = Loaned::loan(Box::new(123u32) as Box<dyn Any>);
+      /// let loaned = loaned.downcast::<i32>().unwrap_err();
+      /// let loaned = loaned.downcast::<u32>().unwrap();
+      /// assert_eq!(borrow.downcast_ref::<u32>(), Some(&123));
+      /// assert_eq!(take!(loaned), Box::new(123));
+      /// ```
+      pub fn downcast<T: core::any::Any>(self) -> Result<Loaned<'t, Box<T>>, Self> {
+        if (**self).is::<T>() {
+          unsafe {
+            let ptr: *mut $Any = mem::transmute_copy(&self.into_raw());
+            Ok(Loaned::from_raw(mem::transmute_copy(&(ptr as *mut T))))
+          }
+        } else {
+          Err(self)
+        }
+      }
+
+      /// Borrows the pointee as a concrete type for `'t`, if it is of that
+      /// type, without ending or changing the loan.
+      ///
+      /// # Example
+      /// ```
+      /// use loaned::{take, Loaned};
+      /// use std::any::Any;
+      /// let (_, loaned) = Loaned::loan(Box::new(123u32) as Box<dyn Any>);
+      /// assert_eq!(loaned.downcast_borrow::<i32>(), None);
+      /// let borrow: &u32 = loaned.downcast_borrow().unwrap();
+      /// assert_eq!(*borrow, 123);
+      /// assert!(take!(loaned).is::<u32>());
+      /// ```
+      #[inline]
+      pub fn downcast_borrow<T: core::any::Any>(&self) -> Option<&'t T> {
+        self.borrow().downcast_ref()
+      }
+    }
+  )*};
+}
+
+downcast_impls!(
+  dyn core::any::Any,
+  dyn core::any::Any + Send,
+  dyn core::any::Any + Send + Sync
+);
+
+impl<'t, T, P: DropPolicy> Deref for Loaned<'t, T, P> {
   type Target = T;
   #[inline(always)]
   fn deref(&self) -> &T {
@@ -116,17 +627,12 @@ impl<'t, T> Deref for Loaned<'t, T> {
   }
 }
 
-impl<'t, T> Drop for Loaned<'t, T> {
-  #[cold]
+impl<'t, T, P: DropPolicy> Drop for Loaned<'t, T, P> {
+  #[inline]
   fn drop(&mut self) {
-    #[cfg(feature = "std")]
-    if mem::needs_drop::<T>() && !std::thread::panicking() {
-      panic!(
-        "memory leak: cannot drop `{Self}`
-    if leaking is desired, use `ManuallyDrop<{Self}>` or `mem::forget`
-    otherwise, use `drop!(loaned)` to drop the inner value",
-        Self = core::any::type_name::<Self>()
-      )
+    if mem::needs_drop::<T>() {
+      stats::count_leak();
+      P::on_leak(core::any::type_name::<Loaned<'t, T>>())
     }
   }
 }
@@ -157,7 +663,7 @@ impl<'t, T: Default> Default for Loaned<'t, T> {
 
 impl<'t, 'u, T: PartialEq<U>, U> PartialEq<Loaned<'u, U>> for Loaned<'t, T> {
   fn eq(&self, other: &Loaned<'u, U>) -> bool {
-    (&**self) == (&**other)
+    **self == **other
   }
 }
 
@@ -181,6 +687,40 @@ impl<'t, T: Hash> Hash for Loaned<'t, T> {
   }
 }
 
+impl<'t, T: Loanable<'t>> AsRef<T::Target> for Loaned<'t, T> {
+  #[inline]
+  fn as_ref(&self) -> &T::Target {
+    self
+  }
+}
+
+macro_rules! borrow_impls {
+  ($($({$($g:tt)*})? $T:ty => $U:ty),* $(,)?) => {$(
+    #[cfg(feature = "alloc")]
+    impl<'t, $($($g)*)?> core::borrow::Borrow<$U> for Loaned<'t, $T> {
+      #[inline]
+      fn borrow(&self) -> &$U {
+        &***self
+      }
+    }
+  )*};
+}
+
+borrow_impls!(
+  alloc::string::String => str,
+  {T} Vec<T> => [T],
+  {T: ?Sized} Box<T> => T,
+  {T: ?Sized} alloc::rc::Rc<T> => T,
+  {T: ?Sized} alloc::sync::Arc<T> => T,
+);
+
+impl<'t, T> From<Loaned<'t, T>> for ManuallyDrop<Loaned<'t, T>> {
+  #[inline(always)]
+  fn from(value: Loaned<'t, T>) -> Self {
+    ManuallyDrop::new(value)
+  }
+}
+
 impl<'t, T> From<T> for Loaned<'t, T> {
   fn from(value: T) -> Self {
     Loaned::new(value)
@@ -190,6 +730,10 @@ impl<'t, T> From<T> for Loaned<'t, T> {
 impl<'t, T> Loaned<'t, T> {
   /// Merges multiple `LoanedMut` values.
   ///
+  /// With the `debug-loans` feature, placing into overlapping places within
+  /// one call (which requires `unsafe` code to obtain aliasing references)
+  /// panics instead of silently overwriting the earlier loan.
+  ///
   /// # Example
   /// ```
   /// use loaned::Loaned;
@@ -203,7 +747,7 @@ impl<'t, T> Loaned<'t, T> {
   pub fn merge(value: T, f: impl for<'i> FnOnce(&'i mut T, &'i Merge<'t, 'i>)) -> Self {
     unsafe {
       let mut inner = RawLoaned::new(value);
-      f(inner.as_mut(), &Merge(PhantomData));
+      f(inner.as_mut(), &Merge(PhantomData, Placements::new()));
       Loaned::from_raw(inner)
     }
   }
@@ -211,12 +755,33 @@ impl<'t, T> Loaned<'t, T> {
 
 /// See [`Loaned::merge`].
 #[doc(hidden)]
-pub struct Merge<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>);
+pub struct Merge<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>, Placements);
 
 impl<'t, 'i> Merge<'t, 'i> {
   /// See [`Loaned::merge`].
-  pub fn place<T>(&'i self, loaned: Loaned<'t, T>, place: &'i mut impl Place<'i, T>) {
-    Place::place(unsafe { LoanedMut::from_raw(loaned.into_raw()) }, place)
+  #[inline(always)]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn place<T>(
+    &'i self,
+    loaned: impl SharedLoanWrapper<'t, Value = T>,
+    place: &'i mut impl Place<'i, T>,
+  ) {
+    self.1.record(place);
+    Place::place(
+      unsafe { LoanedMut::from_raw(loaned.into_shared_loan().into_raw()) },
+      place,
+    )
+  }
+
+  /// See [`Loaned::merge`] and [`Loaned::place_no_drop`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn place_no_drop<T>(
+    &'i self,
+    loaned: impl SharedLoanWrapper<'t, Value = T>,
+    place: &'i mut T,
+  ) {
+    self.1.record(place);
+    unsafe { LoanedMut::from_raw(loaned.into_shared_loan().into_raw()) }.place_no_drop(place)
   }
 }
 
@@ -243,6 +808,74 @@ impl<'t, T> Loaned<'t, T> {
       (loans, Loaned::from_raw(inner))
     }
   }
+
+  /// Combines [`Loaned::merge`] and [`Loaned::loan_with`]: the closure can both
+  /// place loans into the value and loan out parts of it, returning the
+  /// borrows.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let (a, loaned) = Loaned::loan(Box::new(1));
+  /// let (b, ab) = Loaned::merge_with((Box::new(0), Box::new(2)), |ab, m, l| {
+  ///   m.place(loaned, &mut ab.0);
+  ///   l.loan(&mut ab.1)
+  /// });
+  /// assert_eq!(*a + *b, 3);
+  /// assert_eq!(take!(ab), (Box::new(1), Box::new(2)));
+  /// ```
+  pub fn merge_with<L>(
+    value: T,
+    f: impl for<'i> FnOnce(&'i mut T, &'i Merge<'t, 'i>, &'i LoanWith<'t, 'i>) -> L,
+  ) -> (L, Self) {
+    unsafe {
+      let mut inner = RawLoaned::new(value);
+      let loans = f(
+        inner.as_mut(),
+        &Merge(PhantomData, Placements::new()),
+        &LoanWith(PhantomData),
+      );
+      (loans, Loaned::from_raw(inner))
+    }
+  }
+
+  /// Like [`Loaned::loan_with`], but the closure may fail. On failure, the
+  /// error is returned along with the value, which is still loaned (as the
+  /// error may contain loans), but can be taken once `'t` expires.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// let result = Loaned::try_loan_with(vec![1, 2, 3], |v, l| match v.len() {
+  ///   0 => Err("empty"),
+  ///   _ => Ok(l.loan(v)),
+  /// });
+  /// let (borrow, loaned) = result.unwrap();
+  /// assert_eq!(borrow, &[1, 2, 3]);
+  /// assert_eq!(take!(loaned), vec![1, 2, 3]);
+  ///
+  /// let (err, loaned) = Loaned::try_loan_with(vec![], |v: &mut Vec<u32>, l| match v.len() {
+  ///   0 => Err("empty"),
+  ///   _ => Ok(l.loan(v)),
+  /// })
+  /// .unwrap_err();
+  /// assert_eq!(err, "empty");
+  /// assert_eq!(take!(loaned), vec![]);
+  /// ```
+  pub fn try_loan_with<L, E>(
+    value: T,
+    f: impl for<'i> FnOnce(&'i mut T, &'i LoanWith<'t, 'i>) -> Result<L, E>,
+  ) -> Result<(L, Self), (E, Self)> {
+    unsafe {
+      let mut inner = RawLoaned::new(value);
+      let result = f(inner.as_mut(), &LoanWith(PhantomData));
+      let loaned = Loaned::from_raw(inner);
+      match result {
+        Ok(loans) => Ok((loans, loaned)),
+        Err(err) => Err((err, loaned)),
+      }
+    }
+  }
 }
 
 /// See [`Loaned::loan_with`].
@@ -251,7 +884,18 @@ pub struct LoanWith<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>);
 
 impl<'t, 'i> LoanWith<'t, 'i> {
   /// See [`Loaned::loan_with`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
   pub fn loan<T: Loanable<'i>>(&'i self, value: &'i T) -> &'t T::Target {
-    unsafe { &*(&**value as *const _) }
+    let borrow = unsafe { &*(&**value as *const _) };
+    record_loan(borrow);
+    borrow
   }
 }
+
+crate::compile_fail_test!(merge_loaned_mut: "E0277" {
+  let (a, loaned) = loaned::LoanedMut::loan(Box::new(1));
+  let pair = loaned::Loaned::merge((Box::new(0), 2), |pair, m| m.place(loaned, &mut pair.0));
+  let shared: &Box<u32> = &pair.borrow().0;
+  *a = 2;
+  let _ = (shared, loaned::take!(pair));
+});
@@ -19,36 +19,48 @@ use core::fmt::Debug;
 /// there is no way in the type system to enforce this, nor any way to check
 /// this at runtime, dropping a `LoanedMut` panics.
 ///
-/// If leaking is intentional, use a `ManuallyDrop<LoanedMut<'t, T>>`.
+/// If leaking is intentional, use a `ManuallyDrop<LoanedMut<'t, T>>` (e.g. via the
+/// [`leak!`] macro), or [`LoanedMut::forget`].
+/// To leak the value whenever it is dropped (e.g. for a loan embedded in a
+/// public type), use a different [drop policy](policy), as in
+/// `LoanedMut<'t, T, policy::Leak>`.
 ///
 /// To drop the inner value, use the [`drop!`] macro, which will statically ensure
 /// that `'t` has expired.
+///
+/// # Layout
+///
+/// Like [`Loaned`], `LoanedMut<'t, T, P>` is guaranteed to be
+/// `#[repr(transparent)]` over [`RawLoaned<T>`](raw::RawLoaned).
 #[must_use = "dropping a `LoanedMut` panics; use `loaned::drop!` instead"]
 #[repr(transparent)]
-pub struct LoanedMut<'t, T> {
+pub struct LoanedMut<'t, T, P: DropPolicy = Panic> {
   /// Invariant: the target of `inner` is mutably borrowed for `'t`, so it may
   /// not be accessed for the duration of `'t`.
   pub(crate) inner: RawLoaned<T>,
   pub(crate) _contravariant: PhantomData<fn(&'t ())>,
+  pub(crate) _policy: PhantomData<fn() -> P>,
 }
 
 impl<'t, T> LoanedMut<'t, T> {
   /// Constructs a `LoanedMut` from a given smart pointer, returning the mutable
   /// borrow along with the loaned pointer.
   #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
   pub fn loan(value: T) -> (&'t mut T::Target, Self)
   where
     T: Loanable<'t> + DerefMut,
   {
     let mut inner = RawLoaned::new(value);
     let borrow = unsafe { &mut *(&mut **inner.as_mut() as *mut _) };
+    record_loan(borrow);
     (borrow, unsafe { LoanedMut::from_raw(inner) })
   }
 
   /// Creates a `LoanedMut` without actually loaning it. If you want to loan it,
   /// use [`LoanedMut::loan`].
   #[inline(always)]
-  pub fn new(value: T) -> Self {
+  pub const fn new(value: T) -> Self {
     unsafe { LoanedMut::from_raw(RawLoaned::new(value)) }
   }
 
@@ -59,20 +71,668 @@ impl<'t, T> LoanedMut<'t, T> {
     Place::place(self, place)
   }
 
+  /// Like [`LoanedMut::place`] into a `T`, but returns the previous value
+  /// instead of dropping it.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::LoanedMut;
+  /// let mut x = Box::new(0);
+  /// let (a, loaned) = LoanedMut::loan(Box::new(1));
+  /// let old = loaned.swap_place(&mut x);
+  /// *a = 2;
+  /// assert_eq!((old, x), (Box::new(0), Box::new(2)));
+  /// ```
+  #[inline]
+  pub fn swap_place(self, place: &'t mut T) -> T {
+    unsafe {
+      let ptr = place as *mut T;
+      let old = ptr::read(ptr);
+      ptr.cast::<RawLoaned<T>>().write(self.into_raw());
+      old
+    }
+  }
+
+  /// Like [`LoanedMut::place`] into a `T`, but overwrites the previous value
+  /// without dropping it (leaking it, if it owns anything).
+  ///
+  /// This avoids the drop glue when placing into values known to be trivially
+  /// empty, like `None` or `Vec::new()`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::LoanedMut;
+  /// let mut x = None;
+  /// let (a, loaned) = LoanedMut::loan(Box::new(1));
+  /// LoanedMut::<Option<Box<_>>>::from(Some(loaned)).place_no_drop(&mut x);
+  /// *a = 2;
+  /// assert_eq!(x, Some(Box::new(2)));
+  /// ```
+  #[inline]
+  pub fn place_no_drop(self, place: &'t mut T) {
+    unsafe {
+      (place as *mut T)
+        .cast::<RawLoaned<T>>()
+        .write(self.into_raw())
+    }
+  }
+
+  /// Combines two loans into a loan of a tuple. Equivalent to
+  /// `LoanedMut::<(T, U)>::from((self, other))`.
+  ///
+  /// There is no `zip_with`, as the pointees of a `LoanedMut` may not be
+  /// accessed; see [`Loaned::zip_with`].
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+  /// let ab = loaned_a.zip(LoanedMut::new(Box::new(2)));
+  /// *a = 1;
+  /// assert_eq!(take!(ab), (Box::new(1), Box::new(2)));
+  /// ```
+  #[inline]
+  pub fn zip<U>(self, other: LoanedMut<'t, U>) -> LoanedMut<'t, (T, U)> {
+    (self, other).into()
+  }
+
+  /// Intentionally leaks the contained value, without panicking.
+  ///
+  /// This is equivalent to `mem::forget`; see also [`leak!`].
+  #[inline(always)]
+  pub fn forget(self) {
+    mem::forget(self)
+  }
+
+  /// Takes the contained value without statically ensuring that `'t` has
+  /// expired. Prefer [`take!`], which is safe.
+  ///
+  /// # Safety
+  ///
+  /// `'t` must have expired; i.e. none of the borrows loaned out from this
+  /// value may be used again.
   #[inline(always)]
-  pub(crate) fn into_raw(self) -> RawLoaned<T> {
-    unsafe { ptr::read(&ManuallyDrop::new(self).inner) }
+  pub unsafe fn take_unchecked(self) -> T {
+    stats::count_take();
+    MaybeUninit::from(self.into_raw()).assume_init()
   }
 
+  /// Drops the contained value without statically ensuring that `'t` has
+  /// expired. Prefer [`drop!`], which is safe.
+  ///
+  /// # Safety
+  ///
+  /// See [`LoanedMut::take_unchecked`].
   #[inline(always)]
-  pub(crate) unsafe fn from_raw(inner: RawLoaned<T>) -> Self {
+  pub unsafe fn drop_unchecked(self) {
+    mem::drop(self.take_unchecked())
+  }
+
+  /// Mutably borrows the pointee of the value without statically ensuring
+  /// that `'t` has expired, e.g. for containers which track the loan
+  /// externally.
+  ///
+  /// # Safety
+  ///
+  /// None of the borrows loaned out from this value (nor any reference derived
+  /// from them) may be used again, as creating the returned borrow invalidates
+  /// them.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, mut loaned) = LoanedMut::loan(Box::new(1));
+  /// assert_eq!(*borrow, 1);
+  /// // `borrow` is never used again
+  /// unsafe { *loaned.borrow_unchecked_mut() = 2 };
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  #[inline(always)]
+  pub unsafe fn borrow_unchecked_mut(&mut self) -> &mut T::Target
+  where
+    T: DerefMut,
+  {
+    self.inner.as_mut()
+  }
+
+  /// Immutably accesses the contained value, e.g. for debugging a
+  /// partially-built structure. Requires the `unchecked` feature.
+  ///
+  /// # Safety
+  ///
+  /// Reading the value may invalidate any mutable borrows that were loaned out
+  /// from it; the caller must ensure that none of them (nor any reference
+  /// derived from them) are used again. They also must not be used
+  /// concurrently with `f`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(123));
+  /// *borrow = 456;
+  /// // `borrow` is never used again
+  /// let debug = unsafe { loaned.with_unloaned(|x| format!("{x:?}")) };
+  /// assert_eq!(debug, "456");
+  /// assert_eq!(take!(loaned), Box::new(456));
+  /// ```
+  #[cfg(feature = "unchecked")]
+  #[inline]
+  pub unsafe fn with_unloaned<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+    f(self.inner.as_ref())
+  }
+
+  /// Converts this into its raw representation, without ending the loan.
+  ///
+  /// See the [`raw`] module for the requirements on the result.
+  #[inline(always)]
+  pub const fn into_raw(self) -> RawLoaned<T> {
+    // `Self` is `repr(transparent)` over `RawLoaned<T>`.
+    let this = ManuallyDrop::new(self);
+    unsafe { ptr::read(&this as *const ManuallyDrop<Self> as *const RawLoaned<T>) }
+  }
+
+  /// Converts a raw representation back into a `LoanedMut`.
+  ///
+  /// # Safety
+  ///
+  /// `inner` must have come from [`LoanedMut::into_raw`] (or [`RawLoaned::new`])
+  /// with the same `'t`; otherwise, the target of the value must be able to be
+  /// borrowed mutably for the remainder of `'t`.
+  #[inline(always)]
+  pub const unsafe fn from_raw(inner: RawLoaned<T>) -> Self {
     LoanedMut {
       inner,
       _contravariant: PhantomData,
+      _policy: PhantomData,
     }
   }
 }
 
+impl<'t, T, P: DropPolicy> LoanedMut<'t, T, P> {
+  /// Changes the [drop policy](policy) of this loan.
+  ///
+  /// Most of the API of `LoanedMut` is only available with the default policy,
+  /// [`Panic`](policy::Panic).
+  #[inline(always)]
+  pub fn with_policy<Q: DropPolicy>(self) -> LoanedMut<'t, T, Q> {
+    LoanedMut {
+      inner: unsafe { ptr::read(&ManuallyDrop::new(self).inner) },
+      _contravariant: PhantomData,
+      _policy: PhantomData,
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> LoanedMut<'t, Box<T>> {
+  /// Like [`LoanedMut::loan`], but reuses the allocation of `storage` instead
+  /// of allocating a fresh `Box`.
+  ///
+  /// This is useful for recycling allocations across many short-lived loans;
+  /// the allocation can be recovered afterwards with [`take!`].
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// use std::mem::MaybeUninit;
+  /// let storage = Box::new(MaybeUninit::uninit());
+  /// let (borrow, loaned) = LoanedMut::loan_into(storage, 123);
+  /// *borrow += 1;
+  /// assert_eq!(take!(loaned), Box::new(124));
+  /// ```
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_into(storage: Box<MaybeUninit<T>>, value: T) -> (&'t mut T, Self) {
+    let mut storage = storage;
+    storage.write(value);
+    LoanedMut::loan(unsafe { Box::from_raw(Box::into_raw(storage).cast::<T>()) })
+  }
+}
+
+impl<'t, T> LoanedMut<'t, T> {
+  /// Projects the value into one of its fields, preserving the loan. The rest
+  /// of the value is leaked. See also [`Loaned::map`].
+  ///
+  /// # Safety
+  ///
+  /// `f` must return a reference to a field of the value it is passed (not to
+  /// data behind a pointer), and must not access any data loaned by the value
+  /// (e.g. by dereferencing a `Box` within it).
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let ((a, b), ab) = LoanedMut::loan_with((Box::new(0), Box::new(0)), |ab, l| {
+  ///   (l.loan_mut(&mut ab.0), l.loan_mut(&mut ab.1))
+  /// });
+  /// let a2 = unsafe { ab.map_unchecked(|ab| &mut ab.0) };
+  /// *a = 1;
+  /// *b = 2;
+  /// assert_eq!(take!(a2), Box::new(1));
+  /// ```
+  pub unsafe fn map_unchecked<U>(self, f: impl FnOnce(&mut T) -> &mut U) -> LoanedMut<'t, U> {
+    let mut inner = self.into_raw();
+    let field = f(inner.as_mut()) as *mut U;
+    LoanedMut::from_raw(ptr::read(field.cast::<RawLoaned<U>>()))
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> LoanedMut<'t, T> {
+  /// Loans the owned form of a [`Cow`](alloc::borrow::Cow). See
+  /// [`Loaned::loan_cow`].
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_cow<B>(cow: alloc::borrow::Cow<'_, B>) -> (&'t mut B, Self)
+  where
+    B: ?Sized + alloc::borrow::ToOwned<Owned = T>,
+    T: Loanable<'t, Target = B> + DerefMut,
+  {
+    LoanedMut::loan(cow.into_owned())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t> LoanedMut<'t, alloc::string::String> {
+  /// Loans disjoint ranges of a `String`, returning a mutable borrow of each
+  /// range (in the order given) along with the loaned `String`.
+  ///
+  /// If any of the ranges are out of bounds, overlap, or don't lie on char
+  /// boundaries, the `String` is returned instead.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (ranges, loaned) = LoanedMut::loan_str_ranges("a-b-c".into(), &[4..5, 0..1]).unwrap();
+  /// let [c, a] = <[_; 2]>::try_from(ranges).unwrap();
+  /// a.make_ascii_uppercase();
+  /// c.make_ascii_uppercase();
+  /// assert_eq!(take!(loaned), "A-b-C");
+  /// ```
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_str_ranges(
+    value: alloc::string::String,
+    ranges: &[core::ops::Range<usize>],
+  ) -> Result<(Vec<&'t mut str>, Self), alloc::string::String> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|r| r.start);
+    let mut end = 0;
+    for range in &sorted {
+      if range.start < end
+        || range.start > range.end
+        || !value.is_char_boundary(range.start)
+        || !value.is_char_boundary(range.end)
+      {
+        return Err(value);
+      }
+      end = range.end;
+    }
+    let mut inner = RawLoaned::new(value);
+    let base = unsafe { inner.as_mut().as_mut_ptr() };
+    let borrows = ranges
+      .iter()
+      .map(|range| {
+        let bytes = ptr::slice_from_raw_parts_mut(unsafe { base.add(range.start) }, range.len());
+        let borrow = unsafe { &mut *(bytes as *mut str) };
+        record_loan(borrow);
+        borrow
+      })
+      .collect();
+    Ok((borrows, unsafe { LoanedMut::from_raw(inner) }))
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> LoanedMut<'t, Vec<Box<T>>> {
+  /// Allocates `n` boxed values, computed by calling `f` with each index, and
+  /// loans all of them at once, returning a mutable borrow of each along with
+  /// the loaned `Vec`.
+  ///
+  /// This is equivalent to loaning each `Box` individually and collecting the
+  /// results, but avoids the intermediate `Vec<LoanedMut<Box<T>>>`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (nodes, loaned) = LoanedMut::with_capacity_for(3, |i| i * 10);
+  /// for node in nodes {
+  ///   *node += 1;
+  /// }
+  /// assert_eq!(take!(loaned), [1, 11, 21].map(Box::new));
+  /// ```
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn with_capacity_for(n: usize, f: impl FnMut(usize) -> T) -> (Vec<&'t mut T>, Self) {
+    let mut inner = RawLoaned::new((0..n).map(f).map(Box::new).collect::<Vec<_>>());
+    let borrows = unsafe { inner.as_mut() }
+      .iter_mut()
+      .map(|value| {
+        let borrow = unsafe { &mut *(&mut **value as *mut T) };
+        record_loan(borrow);
+        borrow
+      })
+      .collect();
+    (borrows, unsafe { LoanedMut::from_raw(inner) })
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T: ?Sized> LoanedMut<'t, Box<T>> {
+  /// Returns a pointer to the pointee of the loaned `Box`, e.g. for identity
+  /// comparisons or as a key in a pointer-keyed map.
+  ///
+  /// This reads the pointer out of the `Box` without dereferencing it, so
+  /// (unlike going through a reference) it doesn't invalidate the loaned
+  /// borrow. There is no general equivalent for other pointer types, as that
+  /// would require calling [`Deref::deref`] on the loaned pointer.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// assert!(std::ptr::eq(loaned.as_ptr(), borrow));
+  /// *borrow = 2;
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  #[inline(always)]
+  pub fn as_ptr(&self) -> *const T {
+    // `Box<T>` has the same layout as `*const T`.
+    unsafe { ptr::read(self.inner.as_ptr().cast::<*const T>()) }
+  }
+}
+
+impl<'t, P: Loanable<'t> + DerefMut> LoanedMut<'t, Pin<P>> {
+  /// Loans a pinned pointer, returning the pinned mutable borrow along with the
+  /// loaned pointer.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// use std::{marker::PhantomPinned, pin::Pin};
+  /// struct Foo(u32, PhantomPinned);
+  /// impl Foo {
+  ///   fn set(self: Pin<&mut Self>, value: u32) {
+  ///     unsafe { self.get_unchecked_mut().0 = value }
+  ///   }
+  /// }
+  /// let (borrow, loaned) = LoanedMut::loan_pin(Box::pin(Foo(0, PhantomPinned)));
+  /// borrow.set(123);
+  /// assert_eq!(take!(loaned).0, 123);
+  /// ```
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_pin(value: Pin<P>) -> (Pin<&'t mut P::Target>, Self) {
+    let mut inner = RawLoaned::new(value);
+    let borrow = unsafe { &mut *(inner.as_mut().as_mut().get_unchecked_mut() as *mut P::Target) };
+    record_loan(borrow);
+    // The pointee remains pinned, as it is only accessible through `Pin<P>`.
+    (unsafe { Pin::new_unchecked(borrow) }, unsafe {
+      LoanedMut::from_raw(inner)
+    })
+  }
+}
+
+impl<'t, T> LoanedMut<'t, MaybeUninit<T>> {
+  /// Creates an uninitialized `LoanedMut`, whose fields can be initialized
+  /// with loaned values by [`LoanedMut::place_field`].
+  #[inline(always)]
+  pub fn uninit() -> Self {
+    LoanedMut::new(MaybeUninit::uninit())
+  }
+
+  /// Places a loaned value into a field of the uninitialized value, as located
+  /// by `project` (e.g. `|x| unsafe { ptr::addr_of_mut!((*x).field) }`). Any value
+  /// previously placed into the field is leaked.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the pointer returned by `project` does not point to a
+  /// well-aligned location within the value.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// use std::ptr::addr_of_mut;
+  /// struct Pair(Box<u32>, Box<u32>);
+  /// let mut pair = LoanedMut::<std::mem::MaybeUninit<Pair>>::uninit();
+  /// let (a, loaned) = LoanedMut::loan(Box::new(0));
+  /// pair.place_field(loaned, |p| unsafe { addr_of_mut!((*p).0) });
+  /// pair.place_field(LoanedMut::new(Box::new(2)), |p| unsafe { addr_of_mut!((*p).1) });
+  /// let pair = unsafe { pair.assume_init() };
+  /// *a = 1;
+  /// let Pair(a, b) = take!(pair);
+  /// assert_eq!((*a, *b), (1, 2));
+  /// ```
+  pub fn place_field<F>(
+    &mut self,
+    loaned: LoanedMut<'t, F>,
+    project: impl FnOnce(*mut T) -> *mut F,
+  ) {
+    let start = unsafe { self.inner.as_mut() }.as_mut_ptr();
+    let field = project(start);
+    assert!(
+      start as usize <= field as usize
+        && field as usize + mem::size_of::<F>() <= start as usize + mem::size_of::<T>()
+        && field.is_aligned(),
+      "`LoanedMut::place_field` must return a pointer to a field of the value"
+    );
+    unsafe { field.cast::<RawLoaned<F>>().write(loaned.into_raw()) }
+  }
+
+  /// Asserts that the value is initialized, like [`MaybeUninit::assume_init`].
+  ///
+  /// # Safety
+  ///
+  /// The value must be fully initialized.
+  #[inline(always)]
+  pub unsafe fn assume_init(self) -> LoanedMut<'t, T> {
+    LoanedMut::from_raw(mem::transmute_copy(&self.into_raw()))
+  }
+}
+
+impl<'t, T: Loanable<'t>> LoanedMut<'t, T> {
+  /// Narrows the mutable borrow returned by [`LoanedMut::loan`] to a part of
+  /// the pointee, keeping the loan.
+  ///
+  /// This is equivalent to `(f(borrow), self)`, as a `&'t mut` can always be
+  /// reborrowed for `'t` by consuming it; it exists to keep the pair together
+  /// when the narrowing is decided after loaning.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// #[derive(Debug, PartialEq)]
+  /// struct Tree(u32, Option<Box<Tree>>);
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(Tree(0, Some(Box::new(Tree(1, None))))));
+  /// let (child, loaned) = loaned.map_loan(borrow, |t| t.1.as_mut().unwrap());
+  /// child.0 = 2;
+  /// assert_eq!(take!(loaned), Box::new(Tree(0, Some(Box::new(Tree(2, None))))));
+  /// ```
+  #[inline]
+  pub fn map_loan<U: ?Sized>(
+    self,
+    borrow: &'t mut T::Target,
+    f: impl FnOnce(&'t mut T::Target) -> &'t mut U,
+  ) -> (&'t mut U, Self) {
+    (f(borrow), self)
+  }
+
+  /// Converts this into a [`Loaned`], given the mutable borrow returned by
+  /// [`LoanedMut::loan`].
+  ///
+  /// Surrendering the borrow proves that nothing else can access the pointee,
+  /// so it can then be shared with [`Loaned::borrow`]. Note that the resulting
+  /// shared borrows live for `'t`, so the mutable loan cannot be recovered;
+  /// to share the pointee temporarily, use [`LoanedMut::share`] instead.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `borrow` is not a borrow of this value's entire pointee, or if
+  /// the pointee is zero-sized.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// *borrow += 1;
+  /// let loaned = loaned.downgrade(borrow);
+  /// let (a, b) = (loaned.borrow(), loaned.borrow());
+  /// assert_eq!(*a + *b, 4);
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  pub fn downgrade(self, borrow: &'t mut T::Target) -> Loaned<'t, T> {
+    assert!(
+      self.is_borrow(borrow),
+      "`LoanedMut::downgrade` must be passed the borrow of the entire pointee"
+    );
+    unsafe { Loaned::from_raw(self.into_raw()) }
+  }
+
+  /// Temporarily shares the pointee, given the mutable borrow returned by
+  /// [`LoanedMut::loan`], e.g. for a read phase before construction resumes.
+  ///
+  /// `f` is passed a shared borrow of the pointee (which can be copied freely),
+  /// valid only for the duration of the call. Afterwards, the mutable loan is
+  /// recovered along with a fresh mutable borrow of the pointee, which replaces
+  /// `borrow`. A [`Loaned`] can't be lent out instead, as its lifetime could be
+  /// extended to mint borrows outliving the call.
+  ///
+  /// # Panics
+  ///
+  /// See [`LoanedMut::downgrade`].
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// *borrow += 1;
+  /// let (sum, borrow, loaned) = loaned.share(borrow, |x| {
+  ///   let (a, b) = (x, x);
+  ///   *a + *b
+  /// });
+  /// *borrow += sum;
+  /// assert_eq!(take!(loaned), Box::new(6));
+  /// ```
+  pub fn share<R>(
+    mut self,
+    borrow: &'t mut T::Target,
+    f: impl FnOnce(&T::Target) -> R,
+  ) -> (R, &'t mut T::Target, Self)
+  where
+    T: DerefMut,
+  {
+    assert!(
+      self.is_borrow(borrow),
+      "`LoanedMut::share` must be passed the borrow of the entire pointee"
+    );
+    let result = f(unsafe { &**self.inner.as_ref() });
+    let borrow = unsafe { &mut *(&mut **self.inner.as_mut() as *mut _) };
+    (result, borrow, self)
+  }
+
+  /// Like [`LoanedMut::downgrade`], but also returns a shared borrow of the
+  /// pointee, for read-only access after a construction phase. Further shared
+  /// borrows can be minted from the returned [`Loaned`] with
+  /// [`Loaned::borrow`].
+  ///
+  /// # Panics
+  ///
+  /// See [`LoanedMut::downgrade`].
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// *borrow += 1;
+  /// let (frozen, loaned) = loaned.freeze(borrow);
+  /// assert_eq!(*frozen, *loaned.borrow());
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  pub fn freeze(self, borrow: &'t mut T::Target) -> (&'t T::Target, Loaned<'t, T>) {
+    // `borrow` is invalidated by `downgrade`, so a new borrow is needed.
+    let loaned = self.downgrade(borrow);
+    (loaned.borrow(), loaned)
+  }
+
+  /// Shortens the lifetime of the loan to `'s`, given the mutable borrow
+  /// returned by [`LoanedMut::loan`], which is replaced by a new borrow for
+  /// `'s`.
+  ///
+  /// Since `'t` is contravariant, a `LoanedMut<'t, T>` can be used where a
+  /// `LoanedMut` with a longer lifetime is expected, but not a shorter one, as
+  /// that would let it be taken while borrows for `'t` are still live.
+  /// Surrendering the borrow proves that there are none, so e.g. a loan passed
+  /// in from a caller can be taken before the function returns.
+  ///
+  /// # Panics
+  ///
+  /// See [`LoanedMut::downgrade`].
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// fn increment<'t>(borrow: &'t mut u32, loaned: LoanedMut<'t, Box<u32>>) -> Box<u32> {
+  ///   let (borrow, loaned) = loaned.shorten(borrow);
+  ///   *borrow += 1;
+  ///   take!(loaned)
+  /// }
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// assert_eq!(increment(borrow, loaned), Box::new(2));
+  /// ```
+  pub fn shorten<'s>(self, borrow: &'t mut T::Target) -> (&'s mut T::Target, LoanedMut<'s, T>)
+  where
+    T: DerefMut,
+    't: 's,
+  {
+    assert!(
+      self.is_borrow(borrow),
+      "`LoanedMut::shorten` must be passed the borrow of the entire pointee"
+    );
+    // `borrow` is invalidated by `is_borrow`, so it is re-derived, as in
+    // `LoanedMut::loan`.
+    let mut inner = self.into_raw();
+    let borrow = unsafe { &mut *(&mut **inner.as_mut() as *mut _) };
+    (borrow, unsafe { LoanedMut::from_raw(inner) })
+  }
+
+  /// Takes the value before `'t` has expired, given the mutable borrow
+  /// returned by [`LoanedMut::loan`]. If `borrow` is not a borrow of this
+  /// value's entire pointee (or the pointee is zero-sized), `self` is returned
+  /// unchanged; `borrow` is consumed either way.
+  ///
+  /// Like [`LoanedMut::downgrade`], surrendering the borrow proves that nothing
+  /// else can access the pointee. There is no equivalent for `Loaned`, as there
+  /// is no way to know whether all of its shared borrows have been discarded.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::LoanedMut;
+  /// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+  /// *borrow += 1;
+  /// assert_eq!(loaned.try_take(borrow).ok(), Some(Box::new(2)));
+  /// ```
+  pub fn try_take(self, borrow: &'t mut T::Target) -> Result<T, Self> {
+    if self.is_borrow(borrow) {
+      let value = unsafe { self.take_unchecked() };
+      release_taken(&value);
+      Ok(value)
+    } else {
+      Err(self)
+    }
+  }
+
+  /// Checks whether `borrow` is a (non-zero-sized) borrow of the entire
+  /// pointee, and thus must be the only way to access it.
+  ///
+  /// This derefs the loaned pointer, which (like any access to the pointee
+  /// through it) invalidates `borrow`, so it must not be used after this is
+  /// called. Callers that return a borrow must re-derive it from `self.inner`.
+  fn is_borrow(&self, borrow: &mut T::Target) -> bool {
+    let size = mem::size_of_val(borrow);
+    let borrow = borrow as *const T::Target;
+    size != 0 && ptr::eq(borrow, &**unsafe { self.inner.as_ref() })
+  }
+}
+
 impl<'t, T> From<Loaned<'t, T>> for LoanedMut<'t, T> {
   #[inline(always)]
   fn from(value: Loaned<'t, T>) -> Self {
@@ -80,17 +740,12 @@ impl<'t, T> From<Loaned<'t, T>> for LoanedMut<'t, T> {
   }
 }
 
-impl<'t, T> Drop for LoanedMut<'t, T> {
-  #[cold]
+impl<'t, T, P: DropPolicy> Drop for LoanedMut<'t, T, P> {
+  #[inline]
   fn drop(&mut self) {
-    #[cfg(feature = "std")]
-    if mem::needs_drop::<T>() && !std::thread::panicking() {
-      panic!(
-        "memory leak: cannot drop `{Self}`
-    if leaking is desired, use `ManuallyDrop<{Self}>` or `mem::forget`
-    otherwise, use `drop!(loaned)` to drop the inner value",
-        Self = core::any::type_name::<Self>()
-      )
+    if mem::needs_drop::<T>() {
+      stats::count_leak();
+      P::on_leak(core::any::type_name::<LoanedMut<'t, T>>())
     }
   }
 }
@@ -107,6 +762,13 @@ impl<'t, T: Default> Default for LoanedMut<'t, T> {
   }
 }
 
+impl<'t, T> From<LoanedMut<'t, T>> for ManuallyDrop<LoanedMut<'t, T>> {
+  #[inline(always)]
+  fn from(value: LoanedMut<'t, T>) -> Self {
+    ManuallyDrop::new(value)
+  }
+}
+
 impl<'t, T> From<T> for LoanedMut<'t, T> {
   fn from(value: T) -> Self {
     LoanedMut::new(value)
@@ -116,6 +778,10 @@ impl<'t, T> From<T> for LoanedMut<'t, T> {
 impl<'t, T> LoanedMut<'t, T> {
   /// Merges multiple `LoanedMut` values.
   ///
+  /// With the `debug-loans` feature, placing into overlapping places within
+  /// one call (which requires `unsafe` code to obtain aliasing references)
+  /// panics instead of silently overwriting the earlier loan.
+  ///
   /// # Example
   /// ```
   /// use loaned::LoanedMut;
@@ -129,7 +795,7 @@ impl<'t, T> LoanedMut<'t, T> {
   pub fn merge(value: T, f: impl for<'i> FnOnce(&'i mut T, &'i MergeMut<'t, 'i>)) -> Self {
     unsafe {
       let mut inner = RawLoaned::new(value);
-      f(inner.as_mut(), &MergeMut(PhantomData));
+      f(inner.as_mut(), &MergeMut(PhantomData, Placements::new()));
       LoanedMut::from_raw(inner)
     }
   }
@@ -137,12 +803,29 @@ impl<'t, T> LoanedMut<'t, T> {
 
 /// See [`LoanedMut::merge`].
 #[doc(hidden)]
-pub struct MergeMut<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>);
+pub struct MergeMut<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>, Placements);
 
 impl<'t, 'i> MergeMut<'t, 'i> {
   /// See [`LoanedMut::merge`].
-  pub fn place<T>(&'i self, loaned: LoanedMut<'t, T>, place: &'i mut impl Place<'i, T>) {
-    Place::place(unsafe { LoanedMut::from_raw(loaned.into_raw()) }, place)
+  #[inline(always)]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn place<T>(
+    &'i self,
+    loaned: impl LoanWrapper<'t, Value = T>,
+    place: &'i mut impl Place<'i, T>,
+  ) {
+    self.1.record(place);
+    Place::place(
+      unsafe { LoanedMut::from_raw(loaned.into_loan().into_raw()) },
+      place,
+    )
+  }
+
+  /// See [`LoanedMut::merge`] and [`LoanedMut::place_no_drop`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn place_no_drop<T>(&'i self, loaned: impl LoanWrapper<'t, Value = T>, place: &'i mut T) {
+    self.1.record(place);
+    unsafe { LoanedMut::from_raw(loaned.into_loan().into_raw()) }.place_no_drop(place)
   }
 }
 
@@ -169,6 +852,68 @@ impl<'t, T> LoanedMut<'t, T> {
       (loans, LoanedMut::from_raw(inner))
     }
   }
+
+  /// Combines [`LoanedMut::merge`] and [`LoanedMut::loan_with`]: the closure can both
+  /// place loans into the value and loan out parts of it, returning the
+  /// borrows.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (a, loaned) = LoanedMut::loan(Box::new(1));
+  /// let (b, ab) = LoanedMut::merge_with((Box::new(0), Box::new(2)), |ab, m, l| {
+  ///   m.place(loaned, &mut ab.0);
+  ///   l.loan_mut(&mut ab.1)
+  /// });
+  /// assert_eq!(*a + *b, 3);
+  /// assert_eq!(take!(ab), (Box::new(1), Box::new(2)));
+  /// ```
+  pub fn merge_with<L>(
+    value: T,
+    f: impl for<'i> FnOnce(&'i mut T, &'i MergeMut<'t, 'i>, &'i LoanWithMut<'t, 'i>) -> L,
+  ) -> (L, Self) {
+    unsafe {
+      let mut inner = RawLoaned::new(value);
+      let loans = f(
+        inner.as_mut(),
+        &MergeMut(PhantomData, Placements::new()),
+        &LoanWithMut(PhantomData),
+      );
+      (loans, LoanedMut::from_raw(inner))
+    }
+  }
+
+  /// Like [`LoanedMut::loan_with`], but the closure may fail. On failure, the
+  /// error is returned along with the value, which is still loaned (as the
+  /// error may contain loans), but can be taken once `'t` expires.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (err, loaned) = LoanedMut::try_loan_with(Box::new(-1), |x, l| {
+  ///   if **x < 0 {
+  ///     return Err(**x);
+  ///   }
+  ///   Ok(l.loan_mut(x))
+  /// })
+  /// .unwrap_err();
+  /// assert_eq!(err, -1);
+  /// assert_eq!(take!(loaned), Box::new(-1));
+  /// ```
+  pub fn try_loan_with<L, E>(
+    value: T,
+    f: impl for<'i> FnOnce(&'i mut T, &'i LoanWithMut<'t, 'i>) -> Result<L, E>,
+  ) -> Result<(L, Self), (E, Self)> {
+    unsafe {
+      let mut inner = RawLoaned::new(value);
+      let result = f(inner.as_mut(), &LoanWithMut(PhantomData));
+      let loaned = LoanedMut::from_raw(inner);
+      match result {
+        Ok(loans) => Ok((loans, loaned)),
+        Err(err) => Err((err, loaned)),
+      }
+    }
+  }
 }
 
 /// See [`LoanedMut::loan_with`].
@@ -177,11 +922,33 @@ pub struct LoanWithMut<'t, 'i>(PhantomData<(&'t mut &'t (), &'i mut &'i ())>);
 
 impl<'t, 'i> LoanWithMut<'t, 'i> {
   /// See [`LoanedMut::loan_with`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
   pub fn loan_mut<T: Loanable<'i> + DerefMut>(&'i self, value: &'i mut T) -> &'t mut T::Target {
-    unsafe { &mut *(&mut **value as *mut _) }
+    let borrow = unsafe { &mut *(&mut **value as *mut _) };
+    record_loan(borrow);
+    borrow
   }
   /// See [`LoanedMut::loan_with`].
+  #[cfg_attr(feature = "debug-loans", track_caller)]
   pub fn loan<T: Loanable<'i>>(&'i self, value: &'i T) -> &'t T::Target {
-    unsafe { &*(&**value as *const _) }
+    let borrow = unsafe { &*(&**value as *const _) };
+    record_loan(borrow);
+    borrow
   }
 }
+
+crate::compile_fail_test!(take_without_shorten: "E0597" {
+  use loaned::{take, LoanedMut};
+  fn increment<'t>(borrow: &'t mut u32, loaned: LoanedMut<'t, Box<u32>>) -> Box<u32> {
+    *borrow += 1;
+    take!(loaned)
+  }
+});
+
+crate::compile_fail_test!(share_escape: "E0521" {
+  let (borrow, loaned) = loaned::LoanedMut::loan(Box::new(1));
+  let mut shared = Vec::new();
+  let (_, borrow, loaned) = loaned.share(borrow, |x| shared.push(x));
+  *borrow = 2;
+  let _ = (shared, loaned::take!(loaned));
+});
@@ -0,0 +1,192 @@
+#[cfg(feature = "debug-loans")]
+use std::{collections::BTreeMap, panic::Location, sync::Mutex};
+
+use core::ops::Deref;
+
+/// The locations of the current loans, keyed by the address of their pointees.
+#[cfg(feature = "debug-loans")]
+static ORIGINS: Mutex<BTreeMap<usize, &'static Location<'static>>> = Mutex::new(BTreeMap::new());
+
+/// Returns the location at which the value `borrow` points to was loaned (with
+/// e.g. [`Loaned::loan`] or [`LoanedMut::loan`]), if it is known. Requires the
+/// `debug-loans` feature.
+///
+/// The location is forgotten once the loaned value is taken with [`take!`] or
+/// [`drop!`] (or [`drop_all!`]), if it is a pointer or a slice of pointers
+/// (e.g. a `Box<T>` or a merged `Vec<Box<T>>`). Values that are forgotten are
+/// never freed, so their locations are kept.
+///
+/// [`take!`]: crate::take
+/// [`drop!`]: crate::drop
+/// [`drop_all!`]: crate::drop_all
+/// [`Loaned::loan`]: crate::Loaned::loan
+/// [`LoanedMut::loan`]: crate::LoanedMut::loan
+///
+/// # Example
+/// ```
+/// use loaned::{loan_origin, take, LoanedMut};
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(123));
+/// assert_eq!(loan_origin(borrow).unwrap().line(), line!() - 1);
+/// take!(loaned);
+/// ```
+#[cfg(feature = "debug-loans")]
+pub fn loan_origin<T: ?Sized>(borrow: &T) -> Option<&'static Location<'static>> {
+  let origins = ORIGINS.lock().unwrap_or_else(|e| e.into_inner());
+  origins
+    .get(&((borrow as *const T).cast::<()>() as usize))
+    .copied()
+}
+
+#[inline(always)]
+#[cfg_attr(feature = "debug-loans", track_caller)]
+pub(crate) fn record_loan<T: ?Sized>(borrow: &T) {
+  #[cfg(feature = "debug-loans")]
+  {
+    let start = (borrow as *const T).cast::<()>() as usize;
+    let mut origins = ORIGINS.lock().unwrap_or_else(|e| e.into_inner());
+    // Any locations within the pointee are stale, as its memory was reused.
+    remove_range(&mut origins, start, core::mem::size_of_val(borrow));
+    origins.insert(start, Location::caller());
+  }
+  crate::stats::count_loan(core::mem::size_of_val(borrow));
+}
+
+#[cfg(feature = "debug-loans")]
+fn remove_range(
+  origins: &mut BTreeMap<usize, &'static Location<'static>>,
+  start: usize,
+  len: usize,
+) {
+  let mut rest = origins.split_off(&start);
+  origins.append(&mut rest.split_off(&(start + len.max(1))));
+}
+
+/// Forgets the locations of loans within the pointee of `value`.
+#[inline(always)]
+fn release<T: ?Sized>(value: &T) {
+  #[cfg(feature = "debug-loans")]
+  {
+    let start = (value as *const T).cast::<()>() as usize;
+    let mut origins = ORIGINS.lock().unwrap_or_else(|e| e.into_inner());
+    remove_range(&mut origins, start, core::mem::size_of_val(value));
+  }
+  #[cfg(not(feature = "debug-loans"))]
+  let _ = value;
+}
+
+// `take!` and `drop!` forget the locations of the loans in the taken value by
+// dispatching on its type through auto-ref: `(&&&__LoanOrigins(&value))` first
+// tries slices of pointers, then pointers, and then falls back to a no-op.
+
+#[doc(hidden)]
+pub struct __LoanOrigins<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ReleaseSliceOrigins {
+  fn __release_origins(&self);
+}
+
+impl<'a, T: Deref<Target = [U]>, U: Deref> __ReleaseSliceOrigins for &&__LoanOrigins<'a, T> {
+  #[inline(always)]
+  fn __release_origins(&self) {
+    if cfg!(feature = "debug-loans") {
+      for value in self.0.iter() {
+        release(&**value);
+      }
+      release(&**self.0);
+    }
+  }
+}
+
+#[doc(hidden)]
+pub trait __ReleaseOrigins {
+  fn __release_origins(&self);
+}
+
+impl<'a, T: Deref> __ReleaseOrigins for &__LoanOrigins<'a, T> {
+  #[inline(always)]
+  fn __release_origins(&self) {
+    if cfg!(feature = "debug-loans") {
+      release(&**self.0);
+    }
+  }
+}
+
+#[doc(hidden)]
+pub trait __ReleaseNoOrigins {
+  fn __release_origins(&self);
+}
+
+impl<'a, T> __ReleaseNoOrigins for __LoanOrigins<'a, T> {
+  #[inline(always)]
+  fn __release_origins(&self) {}
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __release_origins {
+  ($value:expr) => {{
+    #[allow(unused_imports)]
+    use $crate::{__ReleaseNoOrigins as _, __ReleaseOrigins as _, __ReleaseSliceOrigins as _};
+    (&&&$crate::__LoanOrigins($value)).__release_origins()
+  }};
+}
+
+/// Forgets the locations of the loans in a value taken without [`take!`](crate::take).
+#[inline(always)]
+pub(crate) fn release_taken<'t, T: crate::Loanable<'t>>(value: &T) {
+  if cfg!(feature = "debug-loans") {
+    release(&**value)
+  }
+}
+
+/// The address ranges of the places written to during a single `merge` call,
+/// used to detect overlapping places (which can only be obtained with unsound
+/// `unsafe` code).
+#[cfg(feature = "debug-loans")]
+pub(crate) struct Placements(Mutex<Vec<(usize, usize)>>);
+
+#[cfg(feature = "debug-loans")]
+impl Placements {
+  pub(crate) const fn new() -> Self {
+    Placements(Mutex::new(Vec::new()))
+  }
+
+  #[track_caller]
+  pub(crate) fn record<P>(&self, place: *const P) {
+    let start = place as usize;
+    let end = start + core::mem::size_of::<P>();
+    if start == end {
+      return;
+    }
+    let mut ranges = self.0.lock().unwrap_or_else(|e| e.into_inner());
+    if ranges.iter().any(|&(s, e)| s < end && start < e) {
+      panic!(
+        "overlapping places: `{P}` at {place:p} overlaps a place already used in this merge",
+        P = core::any::type_name::<P>(),
+      )
+    }
+    ranges.push((start, end));
+  }
+}
+
+#[cfg(not(feature = "debug-loans"))]
+pub(crate) struct Placements;
+
+#[cfg(not(feature = "debug-loans"))]
+impl Placements {
+  #[inline(always)]
+  pub(crate) const fn new() -> Self {
+    Placements
+  }
+
+  #[inline(always)]
+  pub(crate) fn record<P>(&self, _: *const P) {}
+}
+
+#[cfg(feature = "std")]
+pub(crate) const DROP_HINT: &str = if cfg!(feature = "debug-loans") {
+  "\n    to find where it was loaned, pass one of its borrows to `loaned::loan_origin`"
+} else {
+  ""
+};
@@ -0,0 +1,138 @@
+//! Building loans in parallel.
+//!
+//! [`build`] runs a closure on several threads, each with its own
+//! [`LoanScope`], and joins the scopes into one once all of the threads have
+//! completed. The borrows loaned on each thread can be returned from it, and
+//! then used to connect the values built on different threads (e.g. through
+//! `&'t` references to atomics or locks).
+//!
+//! With the `rayon` feature, this module also provides parallel iteration over
+//! loaned values (`Loaned::par_iter_loans`) and parallel dropping
+//! (`par_drop!`).
+
+use crate::*;
+#[cfg(feature = "rayon")]
+use ::rayon::prelude::*;
+use std::thread;
+
+/// Runs `f` on `workers` threads, each with the index of the worker and its
+/// own [`LoanScope`], then returns the results of each worker along with a
+/// scope containing the values loaned by all of them, in the order of the
+/// workers.
+///
+/// # Panics
+/// If any worker panics, the panic is propagated once all workers have
+/// completed.
+///
+/// # Example
+/// ```
+/// use loaned::{drop, par};
+/// use std::sync::{atomic::{AtomicU32, Ordering}, OnceLock};
+///
+/// struct Node<'t> {
+///   value: AtomicU32,
+///   next: OnceLock<&'t Node<'t>>,
+/// }
+///
+/// let (nodes, scope) = par::build(4, |i, scope| {
+///   let node = scope.loan_shared(Box::new(Node { value: AtomicU32::new(i as u32), next: OnceLock::new() }));
+///   node.value.fetch_add(10, Ordering::Relaxed);
+///   node
+/// });
+/// for (i, node) in nodes.iter().enumerate() {
+///   node.next.set(nodes[(i + 1) % nodes.len()]).ok().unwrap();
+/// }
+/// assert_eq!(nodes[3].next.get().unwrap().value.load(Ordering::Relaxed), 10);
+/// drop(nodes);
+/// drop!(scope);
+/// ```
+pub fn build<'t, T, R>(
+  workers: usize,
+  f: impl Fn(usize, &mut LoanScope<'t, T>) -> R + Sync,
+) -> (Vec<R>, LoanScope<'t, T>)
+where
+  T: Send,
+  R: Send,
+{
+  let f = &f;
+  let outputs = thread::scope(|s| {
+    let handles = (0..workers)
+      .map(|i| {
+        s.spawn(move || {
+          let mut scope = LoanScope::new();
+          let result = f(i, &mut scope);
+          (result, scope)
+        })
+      })
+      .collect::<Vec<_>>();
+    handles.into_iter().map(|h| h.join()).collect::<Vec<_>>()
+  });
+  let mut results = Vec::with_capacity(workers);
+  let mut joined = LoanScope::new();
+  let mut panic = None;
+  for output in outputs {
+    match output {
+      Ok((result, mut scope)) => {
+        results.push(result);
+        joined.append(&mut scope);
+      }
+      Err(payload) => panic = panic.or(Some(payload)),
+    }
+  }
+  if let Some(payload) = panic {
+    mem::forget(joined);
+    std::panic::resume_unwind(payload);
+  }
+  (results, joined)
+}
+
+#[cfg(feature = "rayon")]
+impl<'t, T> Loaned<'t, T> {
+  /// Like [`Loaned::iter_loans`], but returns a parallel iterator. Requires the
+  /// `rayon` feature.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned};
+  /// use rayon::prelude::*;
+  /// let loaned = Loaned::new((0..100).map(Box::new).collect::<Vec<_>>());
+  /// let sum = loaned.par_iter_loans().copied().sum::<u32>();
+  /// assert_eq!(sum, 4950);
+  /// loaned::par_drop!(loaned);
+  /// ```
+  pub fn par_iter_loans<U>(&self) -> impl IndexedParallelIterator<Item = &'t U::Target> + 't
+  where
+    T: Loanable<'t, Target = [U]>,
+    U: Deref + Sync + 't,
+    U::Target: Sync,
+  {
+    self.borrow().par_iter().map(|x| &**x)
+  }
+}
+
+/// Like [`drop!`], but drops the elements of the value (e.g. the nodes of a
+/// large loaned `Vec<Box<_>>`) in parallel. Requires the `rayon` feature.
+///
+/// The value must implement [`IntoParallelIterator`].
+///
+/// # Example
+/// ```
+/// use loaned::{par_drop, LoanedMut};
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// let loaned = LoanedMut::<Vec<Box<_>>>::from(vec![loaned, LoanedMut::new(Box::new(1))]);
+/// *a = 2;
+/// par_drop!(loaned);
+/// ```
+#[cfg(feature = "rayon")]
+#[macro_export]
+macro_rules! par_drop {
+  ($loaned:expr) => {
+    $crate::__par_drop($crate::take!($loaned))
+  };
+}
+
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub fn __par_drop<I: IntoParallelIterator>(value: I) {
+  value.into_par_iter().for_each(mem::drop)
+}
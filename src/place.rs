@@ -1,20 +1,123 @@
 use crate::*;
 
 /// Abstracts [`Loaned::place`] and [`LoanedMut::place`] for [`take!`] and [`drop!`].
+///
+/// This is implemented for all [`LoanWrapper`]s.
 pub trait Placeable<'t, T>: Sized {
   #[allow(missing_docs)]
   fn place(self, place: &'t mut impl Place<'t, T>);
 }
 
-impl<'t, T> Placeable<'t, T> for Loaned<'t, T> {
-  fn place(self, place: &'t mut impl Place<'t, T>) {
-    self.place(place)
+/// Types which are (or wrap) a loan, and can be converted into a
+/// [`LoanedMut`].
+///
+/// Implementing this for a wrapper type allows it to be used directly with
+/// [`take!`], [`drop!`] (through [`Placeable`]), and [`LoanedMut::merge`],
+/// rather than unwrapping the loan first. Wrappers of shared loans can also
+/// implement [`SharedLoanWrapper`] to be used with [`Loaned::merge`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanWrapper, LoanedMut};
+///
+/// struct TaggedLoan<'t, T> {
+///   tag: &'static str,
+///   loan: LoanedMut<'t, T>,
+/// }
+///
+/// impl<'t, T> LoanWrapper<'t> for TaggedLoan<'t, T> {
+///   type Value = T;
+///   fn into_loan(self) -> LoanedMut<'t, T> {
+///     self.loan
+///   }
+/// }
+///
+/// let (a, loan) = LoanedMut::loan(Box::new(1));
+/// let tagged = TaggedLoan { tag: "a", loan };
+/// assert_eq!(tagged.tag, "a");
+/// let (b, loan) = LoanedMut::loan(Box::new(2));
+/// let pair = LoanedMut::merge((Box::new(0), None), |pair, m| {
+///   m.place(tagged, &mut pair.0);
+///   m.place(TaggedLoan { tag: "b", loan }, &mut pair.1);
+/// });
+/// *a += *b;
+/// assert_eq!(take!(pair), (Box::new(3), Some(Box::new(2))));
+/// ```
+pub trait LoanWrapper<'t>: Sized {
+  /// The type of the loaned value.
+  type Value;
+  /// Converts this into the loan.
+  fn into_loan(self) -> LoanedMut<'t, Self::Value>;
+}
+
+impl<'t, T, P: DropPolicy> LoanWrapper<'t> for Loaned<'t, T, P> {
+  type Value = T;
+  #[inline(always)]
+  fn into_loan(self) -> LoanedMut<'t, T> {
+    self.with_policy::<Panic>().into()
   }
 }
 
-impl<'t, T> Placeable<'t, T> for LoanedMut<'t, T> {
-  fn place(self, place: &'t mut impl Place<'t, T>) {
-    self.place(place)
+/// [`LoanWrapper`]s which wrap a shared loan, and can be converted into a
+/// [`Loaned`].
+///
+/// This is required to place a wrapper with [`Loaned::merge`], as a
+/// [`LoanedMut`] can't be merged into a `Loaned` (which would share the
+/// mutably borrowed pointee).
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanWrapper, Loaned, LoanedMut, SharedLoanWrapper};
+///
+/// struct TaggedLoan<'t, T> {
+///   tag: &'static str,
+///   loan: Loaned<'t, T>,
+/// }
+///
+/// impl<'t, T> LoanWrapper<'t> for TaggedLoan<'t, T> {
+///   type Value = T;
+///   fn into_loan(self) -> LoanedMut<'t, T> {
+///     self.loan.into()
+///   }
+/// }
+///
+/// impl<'t, T> SharedLoanWrapper<'t> for TaggedLoan<'t, T> {
+///   fn into_shared_loan(self) -> Loaned<'t, T> {
+///     self.loan
+///   }
+/// }
+///
+/// let (a, loan) = Loaned::loan(Box::new(1));
+/// let tagged = TaggedLoan { tag: "a", loan };
+/// assert_eq!(tagged.tag, "a");
+/// let pair = Loaned::merge((Box::new(0), 2), |pair, m| m.place(tagged, &mut pair.0));
+/// assert_eq!(*a, 1);
+/// assert_eq!(take!(pair), (Box::new(1), 2));
+/// ```
+pub trait SharedLoanWrapper<'t>: LoanWrapper<'t> {
+  /// Converts this into the shared loan.
+  fn into_shared_loan(self) -> Loaned<'t, Self::Value>;
+}
+
+impl<'t, T, P: DropPolicy> SharedLoanWrapper<'t> for Loaned<'t, T, P> {
+  #[inline(always)]
+  fn into_shared_loan(self) -> Loaned<'t, T> {
+    self.with_policy::<Panic>()
+  }
+}
+
+impl<'t, T, P: DropPolicy> LoanWrapper<'t> for LoanedMut<'t, T, P> {
+  type Value = T;
+  #[inline(always)]
+  fn into_loan(self) -> LoanedMut<'t, T> {
+    self.with_policy::<Panic>()
+  }
+}
+
+impl<'t, L: LoanWrapper<'t>> Placeable<'t, L::Value> for L {
+  #[inline(always)]
+  fn place(self, place: &'t mut impl Place<'t, L::Value>) {
+    self.into_loan().place(place)
   }
 }
 
@@ -55,13 +158,239 @@ impl<'t, T> Place<'t, T> for Option<T> {
   }
 }
 
+/// Placing into a `Box<T>` overwrites its pointee, reusing the existing
+/// allocation (so pointers to it remain valid once `'t` has expired).
+///
+/// # Example
+/// ```
+/// use loaned::LoanedMut;
+/// let mut slot = Box::new(String::new());
+/// let ptr = &*slot as *const String;
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+/// let mut boxed = Box::new(Box::new(0));
+/// loaned.place(&mut boxed);
+/// *borrow = 2;
+/// LoanedMut::new(String::from("abc")).place(&mut slot);
+/// assert_eq!(boxed, Box::new(Box::new(2)));
+/// assert_eq!(*slot, "abc");
+/// assert!(std::ptr::eq(&*slot, ptr));
+/// ```
+#[cfg(feature = "alloc")]
+impl<'t, T> Place<'t, T> for Box<T> {
+  #[inline]
+  fn place(loaned: LoanedMut<'t, T>, place: &'t mut Self) {
+    Place::place(loaned, &mut **place)
+  }
+}
+
+macro_rules! cell_impls {
+  ($($(#[$attr:meta])* $Cell:ident<$P:ty>),*) => {$(
+    $(#[$attr])*
+    impl<'t, T> Place<'t, T> for core::cell::$Cell<$P> {
+      #[inline]
+      fn place(loaned: LoanedMut<'t, T>, place: &'t mut Self) {
+        Place::place(loaned, place.get_mut())
+      }
+    }
+  )*};
+}
+
+cell_impls!(
+  /// Placing into a `Cell` or `RefCell` (of `T` or `Option<T>`) places into its
+  /// contents.
+  ///
+  /// Like any place, the cell remains mutably borrowed for `'t`; placing through
+  /// a shared reference isn't possible, as the cell's other users could then
+  /// replace the loaned value while it is borrowed. Values loaned out by the
+  /// placed value can still hold shared references to other cells, e.g. for
+  /// cyclic links.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::LoanedMut;
+  /// use std::cell::{Cell, RefCell};
+  /// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+  /// let (b, loaned_b) = LoanedMut::loan(Box::new(0));
+  /// let mut cell = Cell::new(None);
+  /// let mut ref_cell = RefCell::new(Box::new(0));
+  /// loaned_a.place(&mut cell);
+  /// loaned_b.place(&mut ref_cell);
+  /// *a = 1;
+  /// *b = 2;
+  /// assert_eq!(cell.into_inner(), Some(Box::new(1)));
+  /// assert_eq!(ref_cell.into_inner(), Box::new(2));
+  /// ```
+  Cell<T>,
+  Cell<Option<T>>,
+  RefCell<T>,
+  RefCell<Option<T>>
+);
+
+/// Pushes loaned values onto a `Vec`, which remains borrowed for `'t`.
+///
+/// # Example
+/// ```
+/// use loaned::{LoanedMut, VecPlace};
+/// let mut vec = Vec::new();
+/// let mut place = VecPlace::new(&mut vec);
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// place.push(loaned);
+/// place.push(LoanedMut::new(Box::new(2)));
+/// *a = 1;
+/// assert_eq!(vec, [Box::new(1), Box::new(2)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct VecPlace<'t, T>(&'t mut Vec<T>);
+
+#[cfg(feature = "alloc")]
+impl<'t, T> VecPlace<'t, T> {
+  /// Borrows `vec` for `'t`, allowing loaned values to be pushed onto it.
+  #[inline]
+  pub fn new(vec: &'t mut Vec<T>) -> Self {
+    VecPlace(vec)
+  }
+
+  /// Pushes a loaned value onto the end of the `Vec`.
+  #[inline]
+  pub fn push(&mut self, loaned: LoanedMut<'t, T>) {
+    self.0.reserve(1);
+    unsafe {
+      let len = self.0.len();
+      let ptr = self.0.as_mut_ptr().add(len);
+      ptr.cast::<RawLoaned<T>>().write(loaned.into_raw());
+      self.0.set_len(len + 1);
+    }
+  }
+
+  /// Returns the number of elements in the `Vec`.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if the `Vec` has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> Extend<LoanedMut<'t, T>> for VecPlace<'t, T> {
+  fn extend<I: IntoIterator<Item = LoanedMut<'t, T>>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.0.reserve(iter.size_hint().0);
+    iter.for_each(|loaned| self.push(loaned))
+  }
+}
+
+/// Inserts loaned values into a `HashMap`, which remains borrowed for `'t`.
+///
+/// Values are stored as `Option<V>`, so that their slots can be created before
+/// placing into them.
+///
+/// # Example
+/// ```
+/// use loaned::{HashMapPlace, LoanedMut};
+/// use std::collections::HashMap;
+/// let (mut map, mut rejected) = (HashMap::new(), None);
+/// let mut place = HashMapPlace::new(&mut map);
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// place.insert("a", loaned).unwrap();
+/// place.insert("b", LoanedMut::new(Box::new(2))).unwrap();
+/// let loaned = place.insert("b", LoanedMut::new(Box::new(3))).unwrap_err();
+/// loaned.place(&mut rejected);
+/// *a = 1;
+/// assert_eq!(map["a"], Some(Box::new(1)));
+/// assert_eq!(map["b"], Some(Box::new(2)));
+/// assert_eq!(rejected, Some(Box::new(3)));
+/// ```
+#[cfg(feature = "std")]
+pub struct HashMapPlace<'t, K, V, S = std::hash::RandomState>(
+  &'t mut std::collections::HashMap<K, Option<V>, S>,
+);
+
+#[cfg(feature = "std")]
+impl<'t, K: Eq + core::hash::Hash, V, S: core::hash::BuildHasher> HashMapPlace<'t, K, V, S> {
+  /// Borrows `map` for `'t`, allowing loaned values to be inserted into it.
+  #[inline]
+  pub fn new(map: &'t mut std::collections::HashMap<K, Option<V>, S>) -> Self {
+    HashMapPlace(map)
+  }
+
+  /// Inserts a loaned value into the map. If the map already contains a value
+  /// for `key` (other than `None`), the loaned value is returned instead.
+  pub fn insert(&mut self, key: K, loaned: LoanedMut<'t, V>) -> Result<(), LoanedMut<'t, V>> {
+    use std::collections::hash_map::Entry;
+    let slot = match self.0.entry(key) {
+      Entry::Occupied(entry) if entry.get().is_some() => return Err(loaned),
+      entry => entry.or_insert(None),
+    };
+    Place::place(loaned, unsafe { &mut *(slot as *mut Option<V>) });
+    Ok(())
+  }
+
+  /// Returns the number of elements in the map.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if the map has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn __into_maybe_uninit<'t, T>(loaned: LoanedMut<'t, T>) -> MaybeUninit<T> {
+  loaned.into_raw().into()
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub unsafe fn __place_variant<E>(place: &mut E, value: MaybeUninit<E>) {
+  let ptr = place as *mut E;
+  ptr::drop_in_place(ptr);
+  ptr.cast::<MaybeUninit<E>>().write(value);
+}
+
 #[inline(always)]
-unsafe fn _maybe_uninit_some<T>(x: MaybeUninit<T>) -> MaybeUninit<Option<T>> {
+pub(crate) unsafe fn _maybe_uninit_some<T>(x: MaybeUninit<T>) -> MaybeUninit<Option<T>> {
   // This is somewhat suspicious but seems to make miri happy.
   //
   // We know that `x` is, in some senses, a valid `T` (i.e. it's initialized,
   // and complies with all the layout requirements of `T`), but we can't use it
   // as a `T` -- in particular, if `T` is a `Box<U>`, moving the box invalidates
   // the mutable references we loaned out.
-  mem::transmute::<_, fn(MaybeUninit<T>) -> MaybeUninit<Option<T>>>(Some::<T> as fn(_) -> _)(x)
+  mem::transmute::<fn(T) -> Option<T>, fn(MaybeUninit<T>) -> MaybeUninit<Option<T>>>(
+    Some::<T> as fn(_) -> _,
+  )(x)
 }
+
+#[inline(always)]
+pub(crate) unsafe fn _maybe_uninit_ok<T, E>(x: MaybeUninit<T>) -> MaybeUninit<Result<T, E>> {
+  // See `_maybe_uninit_some`.
+  mem::transmute::<fn(T) -> Result<T, E>, fn(MaybeUninit<T>) -> MaybeUninit<Result<T, E>>>(
+    Ok::<T, E> as fn(_) -> _,
+  )(x)
+}
+
+#[inline(always)]
+pub(crate) unsafe fn _maybe_uninit_err<T, E>(x: MaybeUninit<E>) -> MaybeUninit<Result<T, E>> {
+  // See `_maybe_uninit_some`.
+  mem::transmute::<fn(E) -> Result<T, E>, fn(MaybeUninit<E>) -> MaybeUninit<Result<T, E>>>(
+    Err::<T, E> as fn(_) -> _,
+  )(x)
+}
+
+#[cfg(feature = "derive")]
+crate::compile_fail_test!(derive_place_named_variant {
+  #[derive(loaned::Place)]
+  enum Shape {
+    Point(i32, i32),
+    Rect { w: i32, h: i32 },
+  }
+});
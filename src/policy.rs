@@ -0,0 +1,80 @@
+//! Policies for what happens when a [`Loaned`](crate::Loaned) or
+//! [`LoanedMut`](crate::LoanedMut) is dropped.
+//!
+//! The value held by a loan can't be dropped while it may still be borrowed, so
+//! dropping a loan always leaks its value; the policy only decides how loudly.
+//! By default, this panics, as it is usually a bug. Types that embed loans in a
+//! public API can use a different policy, e.g. to avoid panicking when a user
+//! drops them:
+//!
+//! ```
+//! use loaned::{policy::Leak, LoanedMut};
+//!
+//! pub struct Handle<'t>(LoanedMut<'t, Box<u32>, Leak>);
+//!
+//! let (r, loaned) = LoanedMut::loan(Box::new(1));
+//! let handle = Handle(loaned.with_policy());
+//! *r = 2;
+//! drop(handle); // leaks the box, rather than panicking
+//! ```
+//!
+//! A loan with any policy can still be taken with [`take!`](crate::take!) or dropped with
+//! [`drop!`](crate::drop!); the rest of the API is available after converting back to the
+//! default policy with `with_policy::<Panic>()`.
+
+/// Decides what happens when a loan holding a value that needs to be dropped
+/// is dropped (leaking the value).
+pub trait DropPolicy {
+  /// Called when a loan of type `type_name` is dropped.
+  fn on_leak(type_name: &'static str);
+}
+
+/// Panics when a loan is dropped (unless already panicking). This is the
+/// default.
+///
+/// Without the `std` feature, this leaks silently, as it is not possible to
+/// check whether the thread is already panicking.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Panic;
+
+impl DropPolicy for Panic {
+  #[cold]
+  #[inline(never)]
+  fn on_leak(type_name: &'static str) {
+    #[cfg(feature = "std")]
+    if !std::thread::panicking() {
+      panic!(
+        "memory leak: cannot drop `{type_name}`
+    if leaking is desired, use `ManuallyDrop<{type_name}>` or `mem::forget`
+    otherwise, use `drop!(loaned)` to drop the inner value{hint}",
+        hint = crate::origin::DROP_HINT,
+      )
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = type_name;
+  }
+}
+
+/// Aborts the process when a loan is dropped.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Abort;
+
+#[cfg(feature = "std")]
+impl DropPolicy for Abort {
+  #[cold]
+  #[inline(never)]
+  fn on_leak(type_name: &'static str) {
+    std::eprintln!("memory leak: cannot drop `{type_name}`; aborting");
+    std::process::abort()
+  }
+}
+
+/// Silently leaks the value when a loan is dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Leak;
+
+impl DropPolicy for Leak {
+  #[inline(always)]
+  fn on_leak(_type_name: &'static str) {}
+}
@@ -0,0 +1,12 @@
+//! The most commonly used items, for glob importing.
+//!
+//! ```
+//! use loaned::prelude::*;
+//! let (borrow, loaned) = LoanedMut::loan(Box::new(1));
+//! *borrow += 1;
+//! assert_eq!(take!(loaned), Box::new(2));
+//! ```
+
+#[cfg(feature = "alloc")]
+pub use crate::LoanExt;
+pub use crate::{drop, drop_all, take, Loanable, Loaned, LoanedMut, Place, Placeable};
@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Types whose loans can be split into loans of each of their fields, and
+/// merged back together.
+///
+/// This should be implemented with `#[derive(LoanProject)]` (with the `derive`
+/// feature enabled), which generates a `{Name}Fields<'t>` struct containing a
+/// `LoanedMut<'t, _>` for each field of `{Name}` (with the same visibility).
+///
+/// [`LoanProject::merge`] doubles as a builder with compile-time required
+/// fields: unlike [`LoanedMut::merge`], it doesn't need a `Default` value to
+/// start from, and omitting a field from the `{Name}Fields` struct is a
+/// compile error. Fields which aren't loaned can be filled with
+/// [`LoanedMut::new`].
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use loaned::{take, LoanProject, LoanedMut};
+///
+/// #[derive(LoanProject, Debug, PartialEq)]
+/// struct Node {
+///   left: Box<u32>,
+///   right: Box<u32>,
+/// }
+///
+/// let (left, a) = LoanedMut::loan(Box::new(0));
+/// let node = Node::merge(NodeFields { left: a, right: LoanedMut::new(Box::new(2)) });
+/// *left = 1;
+/// let NodeFields { left, right } = Node::split(node);
+/// let node = Node::merge(NodeFields { left: right, right: left });
+/// assert_eq!(take!(node), Node { left: Box::new(2), right: Box::new(1) });
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// `Fields` must consist of a `LoanedMut<'t, _>` for each field of `Self`, and
+/// nothing else; `split` and `merge` must move each field into and out of the
+/// corresponding `LoanedMut`.
+pub unsafe trait LoanProject<'t>: Sized {
+  /// The loans of each of the fields of `Self`.
+  type Fields;
+
+  /// Splits a loaned value into loans of each of its fields.
+  fn split(loaned: LoanedMut<'t, Self>) -> Self::Fields;
+
+  /// Merges loans of each of the fields into a loaned value.
+  fn merge(fields: Self::Fields) -> LoanedMut<'t, Self>;
+}
+
+#[doc(hidden)]
+pub unsafe fn __split<'t, T, F>(loaned: LoanedMut<'t, T>, f: impl FnOnce(*mut T) -> F) -> F {
+  let mut inner = ManuallyDrop::new(loaned.into_raw());
+  f(inner.as_mut())
+}
+
+#[doc(hidden)]
+pub unsafe fn __read_field<'t, F>(field: *mut F) -> LoanedMut<'t, F> {
+  LoanedMut::from_raw(ptr::read(field.cast::<RawLoaned<F>>()))
+}
+
+#[doc(hidden)]
+pub unsafe fn __merge<'t, T>(f: impl FnOnce(*mut T)) -> LoanedMut<'t, T> {
+  let mut inner = RawLoaned::new(MaybeUninit::<T>::uninit());
+  f(inner.as_mut().as_mut_ptr());
+  LoanedMut::from_raw(mem::transmute_copy(&ManuallyDrop::new(inner)))
+}
+
+#[doc(hidden)]
+pub unsafe fn __write_field<'t, F>(field: *mut F, loaned: LoanedMut<'t, F>) {
+  field.cast::<RawLoaned<F>>().write(loaned.into_raw())
+}
@@ -0,0 +1,121 @@
+//! Low-level access to the storage of [`Loaned`] and [`LoanedMut`], for
+//! implementing containers that hold loaned values.
+//!
+//! A [`RawLoaned<T>`] is a `T` that the compiler is not allowed to treat as a
+//! `T` -- it is never dropped, and moving it does not assert the uniqueness of
+//! any pointers within it. This is what allows a `Box<T>` to be moved while its
+//! contents are mutably borrowed.
+//!
+//! [`Loaned`] and [`LoanedMut`] are `#[repr(transparent)]` wrappers around
+//! `RawLoaned<T>`, which is in turn laid out the same as `T`; a container can
+//! store loaned values as `RawLoaned<T>` (or `MaybeUninit<T>`) and convert back
+//! with [`Loaned::from_raw`] or [`LoanedMut::from_raw`].
+//!
+//! # Safety
+//!
+//! The value inside a `RawLoaned<T>` obtained from [`Loaned::into_raw`] or
+//! [`LoanedMut::into_raw`] may still be borrowed. In particular:
+//! - it must not be dropped or moved as a `T` (e.g. by [`MaybeUninit::assume_init`])
+//!   until the loan has expired, as that may invalidate the loaned borrows;
+//! - dereferencing it (e.g. through [`RawLoaned::as_ref`]) is only allowed in
+//!   the ways the original loan allowed (shared access for [`Loaned`], none for
+//!   [`LoanedMut`]);
+//! - it must eventually be converted back with `from_raw` (or otherwise
+//!   disposed of in a way that keeps the borrows valid).
+//!
+//! # Example
+//! ```
+//! use loaned::{raw::RawLoaned, take, LoanedMut};
+//!
+//! struct Slots<'t, T>(Vec<Option<RawLoaned<T>>>, std::marker::PhantomData<&'t mut T>);
+//!
+//! impl<'t, T> Slots<'t, T> {
+//!   fn insert(&mut self, loaned: LoanedMut<'t, T>) -> usize {
+//!     self.0.push(Some(loaned.into_raw()));
+//!     self.0.len() - 1
+//!   }
+//!   fn remove(&mut self, index: usize) -> Option<LoanedMut<'t, T>> {
+//!     // SAFETY: the value came from `LoanedMut::into_raw` with the same `'t`.
+//!     Some(unsafe { LoanedMut::from_raw(self.0[index].take()?) })
+//!   }
+//! }
+//!
+//! let mut slots = Slots(Vec::new(), std::marker::PhantomData);
+//! let (a, loaned) = LoanedMut::loan(Box::new(1));
+//! let i = slots.insert(loaned);
+//! *a = 2;
+//! let loaned = slots.remove(i).unwrap();
+//! assert_eq!(take!(loaned), Box::new(2));
+//! ```
+
+use crate::*;
+
+/// A `T` that may be borrowed, and must not be used as a `T`.
+///
+/// This is a `#[repr(C)]` union with a single field, so it has the same size
+/// and alignment as `T`. See the [module documentation](self) for details.
+#[repr(C)]
+pub union RawLoaned<T> {
+  value: ManuallyDrop<T>,
+}
+
+impl<T> RawLoaned<T> {
+  /// Wraps a value that is not borrowed.
+  #[inline(always)]
+  pub const fn new(value: T) -> Self {
+    RawLoaned {
+      value: ManuallyDrop::new(value),
+    }
+  }
+
+  /// Returns a mutable reference to the value.
+  ///
+  /// # Safety
+  ///
+  /// The value must not be borrowed.
+  #[inline(always)]
+  pub unsafe fn as_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+
+  /// Returns a shared reference to the value.
+  ///
+  /// # Safety
+  ///
+  /// The value must not be mutably borrowed.
+  #[inline(always)]
+  pub unsafe fn as_ref(&self) -> &T {
+    &self.value
+  }
+
+  /// Returns a pointer to the value, without asserting anything about its
+  /// borrows.
+  #[inline(always)]
+  pub const fn as_ptr(&self) -> *const T {
+    self as *const Self as *const T
+  }
+
+  /// Returns a mutable pointer to the value, without asserting anything about
+  /// its borrows.
+  #[inline(always)]
+  pub const fn as_mut_ptr(&mut self) -> *mut T {
+    self as *mut Self as *mut T
+  }
+}
+
+impl<T> RawLoaned<T> {
+  /// Converts the value into a `MaybeUninit`, without asserting anything
+  /// about its borrows. This is the same as the `From` impl, but usable in
+  /// const contexts.
+  #[inline(always)]
+  pub const fn into_maybe_uninit(self) -> MaybeUninit<T> {
+    unsafe { ptr::read(&self as *const Self as *const MaybeUninit<T>) }
+  }
+}
+
+impl<T> From<RawLoaned<T>> for MaybeUninit<T> {
+  #[inline(always)]
+  fn from(value: RawLoaned<T>) -> Self {
+    value.into_maybe_uninit()
+  }
+}
@@ -0,0 +1,148 @@
+use crate::*;
+use alloc::{
+  rc::{self, Rc},
+  sync::{self, Arc},
+};
+use core::cell::RefCell;
+
+/// A reference-counted allocation, immutably loaned for `'t`.
+///
+/// Cloning a `LoanedRc` clones the `Rc`, producing another `LoanedRc` which
+/// must also be taken with [`take!`] once `'t` has expired. Alternatively,
+/// plain `Rc`s can be cloned from it with `Rc::clone(&loaned)`; these may be
+/// dropped at any point, as the `LoanedRc` keeps the allocation alive for the
+/// remainder of `'t`.
+///
+/// # Example
+/// ```
+/// use loaned::{take, Loaned, LoanedRc};
+/// use std::rc::Rc;
+/// let (borrow, a): (_, LoanedRc<u32>) = Loaned::loan(Rc::new(123));
+/// let b = a.clone();
+/// let c = Rc::clone(&a);
+/// drop(c);
+/// assert_eq!(*borrow, 123);
+/// assert_eq!(Rc::strong_count(&take!(a)), 2);
+/// assert_eq!(Rc::into_inner(take!(b)), Some(123));
+/// ```
+pub type LoanedRc<'t, T> = Loaned<'t, Rc<T>>;
+
+/// An atomically reference-counted allocation, immutably loaned for `'t`.
+///
+/// See [`LoanedRc`].
+pub type LoanedArc<'t, T> = Loaned<'t, Arc<T>>;
+
+/// A reference-counted, interior-mutable allocation, immutably loaned for
+/// `'t`.
+///
+/// This is the loaned counterpart of the common `Rc<RefCell<T>>` node type.
+/// Loaning one yields an `&'t RefCell<T>`, which can be stored elsewhere (e.g.
+/// in the edges of a graph) and mutated through for the whole of `'t`, while
+/// the `LoanedRcCell` owns the allocation. The contents can also be accessed
+/// through the loan with [`Loaned::borrow_cell`] and
+/// [`Loaned::borrow_cell_mut`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, Loaned, LoanedRcCell};
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let (a, loaned_a): (_, LoanedRcCell<u32>) = Loaned::loan(Rc::new(RefCell::new(1)));
+/// let (b, loaned_b): (_, LoanedRcCell<u32>) = Loaned::loan(Rc::new(RefCell::new(2)));
+/// let edges: Vec<(&RefCell<u32>, &RefCell<u32>)> = vec![(a, b), (b, a), (a, a)];
+/// for (from, to) in &edges {
+///   *to.borrow_mut() += 1;
+///   *from.borrow_mut() *= 2;
+/// }
+/// *loaned_b.borrow_cell_mut() += 10;
+/// assert_eq!(*loaned_a.borrow_cell(), 8);
+/// drop(edges);
+/// assert_eq!(*take!(loaned_a).borrow(), 8);
+/// assert_eq!(*take!(loaned_b).borrow(), 16);
+/// ```
+pub type LoanedRcCell<'t, T> = Loaned<'t, Rc<RefCell<T>>>;
+
+impl<'t, T: ?Sized> LoanedRc<'t, T> {
+  /// Creates a [`Weak`](rc::Weak) pointer to the loaned allocation, like
+  /// [`Rc::downgrade`].
+  ///
+  /// The loaned `Rc` is a strong reference, so the weak pointer can be
+  /// upgraded for as long as the loan is held, and then for as long as the
+  /// taken `Rc` (or another strong reference) is kept. Upgrading produces an
+  /// ordinary `Rc`, which only provides shared access, just like the loan.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, Loaned, LoanedRc};
+  /// use std::rc::Rc;
+  /// let (borrow, loaned): (_, LoanedRc<u32>) = Loaned::loan(Rc::new(123));
+  /// let weak = LoanedRc::downgrade(&loaned);
+  /// assert_eq!(weak.upgrade().as_deref(), Some(borrow));
+  /// let rc = take!(loaned);
+  /// assert_eq!(weak.upgrade().as_deref(), Some(&123));
+  /// drop(rc);
+  /// assert_eq!(weak.upgrade(), None);
+  /// ```
+  #[inline]
+  pub fn downgrade(&self) -> rc::Weak<T> {
+    Rc::downgrade(self)
+  }
+}
+
+impl<'t, T: ?Sized> LoanedArc<'t, T> {
+  /// Creates a [`Weak`](sync::Weak) pointer to the loaned allocation, like
+  /// [`Arc::downgrade`]. See [`LoanedRc::downgrade`].
+  #[inline]
+  pub fn downgrade(&self) -> sync::Weak<T> {
+    Arc::downgrade(self)
+  }
+}
+
+/// Reference-counted pointers which can be unwrapped into their inner value if
+/// they are the only strong reference, like [`Rc::try_unwrap`].
+///
+/// This allows [`try_unwrap!`] to be used generically.
+pub trait TryUnwrap: Deref + Sized {
+  /// The type of the inner value.
+  type Inner;
+  /// Returns the inner value if `self` is the only strong reference, or
+  /// `self` otherwise.
+  fn try_unwrap(this: Self) -> Result<Self::Inner, Self>;
+}
+
+impl<T> TryUnwrap for Rc<T> {
+  type Inner = T;
+  #[inline]
+  fn try_unwrap(this: Self) -> Result<T, Self> {
+    Rc::try_unwrap(this)
+  }
+}
+
+impl<T> TryUnwrap for Arc<T> {
+  type Inner = T;
+  #[inline]
+  fn try_unwrap(this: Self) -> Result<T, Self> {
+    Arc::try_unwrap(this)
+  }
+}
+
+/// Takes a loaned `Rc` or `Arc` (or any [`TryUnwrap`] pointer), statically
+/// ensuring that `'t` is expired like [`take!`], and then tries to unwrap it.
+///
+/// # Example
+/// ```
+/// use loaned::{try_unwrap, Loaned, LoanedRc};
+/// use std::rc::Rc;
+/// let (borrow, a): (_, LoanedRc<u32>) = Loaned::loan(Rc::new(123));
+/// let b = a.clone();
+/// assert_eq!(*borrow, 123);
+/// let a = try_unwrap!(a).unwrap_err();
+/// drop(a);
+/// assert_eq!(try_unwrap!(b), Ok(123));
+/// ```
+#[macro_export]
+macro_rules! try_unwrap {
+  ($loaned:expr) => {
+    $crate::TryUnwrap::try_unwrap($crate::take!($loaned))
+  };
+}
@@ -0,0 +1,32 @@
+use crate::*;
+use std::thread::{self, Scope};
+
+/// Loans a value for the duration of a [`std::thread::scope`], passing the
+/// borrow to `f` (which can share it with threads spawned on the scope), then
+/// returns the result of `f` along with the value.
+///
+/// All threads spawned on the scope are joined before the value is returned.
+///
+/// # Example
+/// ```
+/// let data = Box::new([1, 2, 3, 4]);
+/// let (sum, data) = loaned::scope(data, |s, data| {
+///   let (a, b) = data.split_at(2);
+///   let a = s.spawn(move || a.iter().sum::<u32>());
+///   let b = s.spawn(move || b.iter().sum::<u32>());
+///   a.join().unwrap() + b.join().unwrap()
+/// });
+/// assert_eq!(sum, 10);
+/// assert_eq!(data, Box::new([1, 2, 3, 4]));
+/// ```
+pub fn scope<T, R>(
+  value: T,
+  f: impl for<'scope, 'env> FnOnce(&'scope Scope<'scope, 'env>, &'env T::Target) -> R,
+) -> (R, T)
+where
+  T: for<'t> Loanable<'t>,
+{
+  let (borrow, loaned) = Loaned::loan(value);
+  let result = thread::scope(|s| f(s, borrow));
+  (result, take!(loaned))
+}
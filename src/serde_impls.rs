@@ -0,0 +1,16 @@
+use crate::*;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes the contained value, which may be accessed immutably.
+impl<'t, T: Serialize> Serialize for Loaned<'t, T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    T::serialize(self, serializer)
+  }
+}
+
+/// Deserializes a value with [`Loaned::new`].
+impl<'de, 't, T: Deserialize<'de>> Deserialize<'de> for Loaned<'t, T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    T::deserialize(deserializer).map(Loaned::new)
+  }
+}
@@ -0,0 +1,458 @@
+use crate::*;
+
+/// A mutable borrow of a slice loaned for `'t`, which can be split into
+/// disjoint parts, each of which can be placed into independently.
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, LoanedSlice};
+/// let (slice, loaned) = LoanedSlice::loan(vec![None, None, None]);
+/// let (a, b) = slice.split_at(1);
+/// LoanedMut::new(Box::new(0)).place(&mut a.into_slice()[0]);
+/// for (i, slot) in b.into_slice().iter_mut().enumerate() {
+///   *slot = Some(Box::new(i + 1));
+/// }
+/// assert_eq!(take!(loaned), [Some(Box::new(0)), Some(Box::new(1)), Some(Box::new(2))]);
+/// ```
+pub struct LoanedSlice<'t, T>(&'t mut [T]);
+
+impl<'t, T> LoanedSlice<'t, T> {
+  /// Loans a slice-like value, returning the borrow of its slice along with
+  /// the loaned value.
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan<P>(value: P) -> (Self, LoanedMut<'t, P>)
+  where
+    P: Loanable<'t, Target = [T]> + DerefMut,
+  {
+    let (slice, loaned) = LoanedMut::loan(value);
+    (LoanedSlice(slice), loaned)
+  }
+
+  /// Returns the number of elements in the slice.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if the slice has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Splits the slice into two at an index, like [`slice::split_at_mut`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `mid > len`.
+  #[inline]
+  pub fn split_at(self, mid: usize) -> (Self, Self) {
+    let (a, b) = self.0.split_at_mut(mid);
+    (LoanedSlice(a), LoanedSlice(b))
+  }
+
+  /// Splits off the first element of the slice, if it is not empty.
+  #[inline]
+  pub fn split_first(self) -> Option<(&'t mut T, Self)> {
+    let (first, rest) = self.0.split_first_mut()?;
+    Some((first, LoanedSlice(rest)))
+  }
+
+  /// Removes the first element from the slice, returning a borrow of it.
+  #[inline]
+  pub fn take_first(&mut self) -> Option<&'t mut T> {
+    let (first, rest) = mem::take(&mut self.0).split_first_mut()?;
+    self.0 = rest;
+    Some(first)
+  }
+
+  /// Returns the underlying borrow.
+  #[inline]
+  pub fn into_slice(self) -> &'t mut [T] {
+    self.0
+  }
+}
+
+impl<'t, T> From<&'t mut [T]> for LoanedSlice<'t, T> {
+  #[inline]
+  fn from(value: &'t mut [T]) -> Self {
+    LoanedSlice(value)
+  }
+}
+
+impl<'t, T> Default for LoanedSlice<'t, T> {
+  #[inline]
+  fn default() -> Self {
+    LoanedSlice(&mut [])
+  }
+}
+
+impl<'t, T> Deref for LoanedSlice<'t, T> {
+  type Target = [T];
+  #[inline]
+  fn deref(&self) -> &[T] {
+    self.0
+  }
+}
+
+impl<'t, T> DerefMut for LoanedSlice<'t, T> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut [T] {
+    self.0
+  }
+}
+
+/// The elements of a loaned slice, each of which can be placed into once, by
+/// index, with [`LoanedMut::place_at`].
+///
+/// Unlike [`LoanedSlice`], which must be split to place into multiple
+/// elements, a `SliceHoles` tracks which elements are still holes, so loans can
+/// be placed into any of them in any order. The elements must already be
+/// initialized (e.g. to `None`); to build a slice from uninitialized elements,
+/// use [`SliceBuilder`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, SliceHoles};
+/// let (mut holes, loaned) = SliceHoles::loan(vec![None, None, None].into_boxed_slice());
+/// let (a, loaned_a) = LoanedMut::loan(Box::new(0));
+/// loaned_a.place_at(&mut holes, 2).unwrap();
+/// LoanedMut::new(Box::new(1)).place_at(&mut holes, 0).unwrap();
+/// let rejected = LoanedMut::new(Box::new(4)).place_at(&mut holes, 0).unwrap_err();
+/// assert_eq!(holes.remaining(), 1);
+/// *a = 3;
+/// assert_eq!(take!(loaned), [Some(Box::new(1)), None, Some(Box::new(3))].into());
+/// loaned::drop!(rejected);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct SliceHoles<'t, T> {
+  holes: Vec<Option<&'t mut T>>,
+  remaining: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> SliceHoles<'t, T> {
+  /// Loans a slice-like value, returning the holes of its slice along with
+  /// the loaned value.
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan<P>(value: P) -> (Self, LoanedMut<'t, P>)
+  where
+    P: Loanable<'t, Target = [T]> + DerefMut,
+  {
+    let (slice, loaned) = LoanedSlice::loan(value);
+    (SliceHoles::from(slice), loaned)
+  }
+
+  /// Returns the number of elements in the slice.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.holes.len()
+  }
+
+  /// Returns `true` if the slice has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.holes.is_empty()
+  }
+
+  /// Returns the number of elements which are still holes.
+  #[inline]
+  pub fn remaining(&self) -> usize {
+    self.remaining
+  }
+
+  /// Returns `true` if the element at `index` is still a hole.
+  #[inline]
+  pub fn is_hole(&self, index: usize) -> bool {
+    matches!(self.holes.get(index), Some(Some(_)))
+  }
+
+  /// Removes the element at `index` from the holes, returning a borrow of
+  /// it, if it is still a hole.
+  #[inline]
+  pub fn take_hole(&mut self, index: usize) -> Option<&'t mut T> {
+    let hole = self.holes.get_mut(index)?.take()?;
+    self.remaining -= 1;
+    Some(hole)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> From<LoanedSlice<'t, T>> for SliceHoles<'t, T> {
+  fn from(slice: LoanedSlice<'t, T>) -> Self {
+    SliceHoles::from(slice.into_slice())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> From<&'t mut [T]> for SliceHoles<'t, T> {
+  fn from(slice: &'t mut [T]) -> Self {
+    SliceHoles {
+      remaining: slice.len(),
+      holes: slice.iter_mut().map(Some).collect(),
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> core::fmt::Debug for SliceHoles<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "SliceHoles(..)")
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> LoanedMut<'t, T> {
+  /// Places this into the element of `holes` at `index` (which may be any
+  /// [`Place`] for `T`, e.g. `Option<T>`). If `index` is out of bounds, or the
+  /// element has already been placed into, `self` is returned instead.
+  ///
+  /// See [`SliceHoles`].
+  pub fn place_at<S: Place<'t, T>>(
+    self,
+    holes: &mut SliceHoles<'t, S>,
+    index: usize,
+  ) -> Result<(), Self> {
+    match holes.take_hole(index) {
+      Some(hole) => {
+        self.place(hole);
+        Ok(())
+      }
+      None => Err(self),
+    }
+  }
+}
+
+/// Builds a [`LoanedMut<'t, Box<[T]>>`](LoanedMut) by placing loaned values
+/// into its elements in any order, tracking which have been initialized.
+///
+/// # Dropping
+///
+/// Like [`LoanedMut`], dropping a `SliceBuilder` which contains values panics
+/// (or leaks, without the `std` feature).
+///
+/// # Example
+/// ```
+/// use loaned::{drop, take, LoanedMut, SliceBuilder};
+/// let mut builder = SliceBuilder::new(3);
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// builder.place(2, loaned).unwrap();
+/// builder.place(0, LoanedMut::new(Box::new(1))).unwrap();
+/// let rejected = builder.place(0, LoanedMut::new(Box::new(4))).unwrap_err();
+/// builder.place(1, LoanedMut::new(Box::new(2))).unwrap();
+/// let loaned = builder.finish().unwrap();
+/// *a = 3;
+/// assert_eq!(*take!(loaned), [Box::new(1), Box::new(2), Box::new(3)]);
+/// drop!(rejected);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct SliceBuilder<'t, T> {
+  slots: Box<[MaybeUninit<T>]>,
+  init: Box<[bool]>,
+  remaining: usize,
+  _contravariant: PhantomData<fn(&'t ())>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> SliceBuilder<'t, T> {
+  /// Constructs a `SliceBuilder` for a slice of `len` elements, none of which
+  /// are initialized.
+  pub fn new(len: usize) -> Self {
+    SliceBuilder {
+      slots: (0..len).map(|_| MaybeUninit::uninit()).collect(),
+      init: (0..len).map(|_| false).collect(),
+      remaining: len,
+      _contravariant: PhantomData,
+    }
+  }
+
+  /// Returns the number of elements in the slice.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.slots.len()
+  }
+
+  /// Returns `true` if the slice has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.slots.is_empty()
+  }
+
+  /// Returns `true` if the element at `index` has been initialized.
+  #[inline]
+  pub fn is_placed(&self, index: usize) -> bool {
+    self.init.get(index).copied().unwrap_or(false)
+  }
+
+  /// Places a loaned value into the element at `index`. If `index` is out of
+  /// bounds, or the element has already been initialized, the loaned value is
+  /// returned instead.
+  pub fn place(&mut self, index: usize, loaned: LoanedMut<'t, T>) -> Result<(), LoanedMut<'t, T>> {
+    match self.init.get_mut(index) {
+      Some(init @ false) => {
+        *init = true;
+        self.slots[index] = loaned.into_raw().into();
+        self.remaining -= 1;
+        Ok(())
+      }
+      _ => Err(loaned),
+    }
+  }
+
+  /// Converts the builder into the loaned slice, if all of its elements have
+  /// been initialized.
+  pub fn finish(self) -> Result<LoanedMut<'t, Box<[T]>>, Self> {
+    if self.remaining != 0 {
+      return Err(self);
+    }
+    let mut this = ManuallyDrop::new(self);
+    unsafe {
+      ptr::drop_in_place(&mut this.init);
+      let slots = ptr::read(&this.slots);
+      Ok(LoanedMut::new(Box::from_raw(
+        Box::into_raw(slots) as *mut [T]
+      )))
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> core::fmt::Debug for SliceBuilder<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "SliceBuilder(..)")
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, T> Drop for SliceBuilder<'t, T> {
+  #[cold]
+  fn drop(&mut self) {
+    #[cfg(feature = "std")]
+    if mem::needs_drop::<T>() && self.remaining != self.len() && !std::thread::panicking() {
+      panic!(
+        "memory leak: cannot drop `{Self}` containing values
+    if leaking is desired, use `ManuallyDrop<{Self}>` or `mem::forget`",
+        Self = core::any::type_name::<Self>()
+      )
+    }
+  }
+}
+
+/// Builds a [`LoanedMut<'t, [T; N]>`](LoanedMut) by placing loaned values into
+/// its elements in any order, tracking which have been initialized.
+///
+/// Like [`SliceBuilder`], but without allocating. Indices passed to
+/// [`ArrayBuilder::place_at`] are bounds-checked at compile time.
+///
+/// # Dropping
+///
+/// Like [`LoanedMut`], dropping an `ArrayBuilder` which contains values panics
+/// (or leaks, without the `std` feature).
+///
+/// # Example
+/// ```
+/// use loaned::{take, ArrayBuilder, LoanedMut};
+/// let mut builder = ArrayBuilder::<_, 3>::new();
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// builder.place_at::<2>(loaned).unwrap();
+/// builder.place(0, LoanedMut::new(Box::new(1))).unwrap();
+/// let mut builder = builder.finish().unwrap_err();
+/// builder.place_at::<1>(LoanedMut::new(Box::new(2))).unwrap();
+/// let loaned = builder.finish().unwrap();
+/// *a = 3;
+/// assert_eq!(take!(loaned), [Box::new(1), Box::new(2), Box::new(3)]);
+/// ```
+pub struct ArrayBuilder<'t, T, const N: usize> {
+  slots: [MaybeUninit<T>; N],
+  init: [bool; N],
+  remaining: usize,
+  _contravariant: PhantomData<fn(&'t ())>,
+}
+
+impl<'t, T, const N: usize> ArrayBuilder<'t, T, N> {
+  /// Constructs an `ArrayBuilder`, none of whose elements are initialized.
+  #[inline]
+  pub const fn new() -> Self {
+    ArrayBuilder {
+      slots: [const { MaybeUninit::uninit() }; N],
+      init: [false; N],
+      remaining: N,
+      _contravariant: PhantomData,
+    }
+  }
+
+  /// Returns `true` if the element at `index` has been initialized.
+  #[inline]
+  pub fn is_placed(&self, index: usize) -> bool {
+    self.init.get(index).copied().unwrap_or(false)
+  }
+
+  /// Places a loaned value into the element at `index`. If `index` is out of
+  /// bounds, or the element has already been initialized, the loaned value is
+  /// returned instead.
+  pub fn place(&mut self, index: usize, loaned: LoanedMut<'t, T>) -> Result<(), LoanedMut<'t, T>> {
+    match self.init.get_mut(index) {
+      Some(init @ false) => {
+        *init = true;
+        self.slots[index] = loaned.into_raw().into();
+        self.remaining -= 1;
+        Ok(())
+      }
+      _ => Err(loaned),
+    }
+  }
+
+  /// Like [`ArrayBuilder::place`], but fails to compile if `I` is out of
+  /// bounds.
+  #[inline]
+  pub fn place_at<const I: usize>(
+    &mut self,
+    loaned: LoanedMut<'t, T>,
+  ) -> Result<(), LoanedMut<'t, T>> {
+    const { assert!(I < N, "index out of bounds") };
+    self.place(I, loaned)
+  }
+
+  /// Converts the builder into the loaned array, if all of its elements have
+  /// been initialized.
+  pub fn finish(self) -> Result<LoanedMut<'t, [T; N]>, Self> {
+    if self.remaining != 0 {
+      return Err(self);
+    }
+    let this = ManuallyDrop::new(self);
+    Ok(unsafe { LoanedMut::from_raw(mem::transmute_copy(&this.slots)) })
+  }
+}
+
+impl<'t, T, const N: usize> Default for ArrayBuilder<'t, T, N> {
+  fn default() -> Self {
+    ArrayBuilder::new()
+  }
+}
+
+impl<'t, T, const N: usize> core::fmt::Debug for ArrayBuilder<'t, T, N> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "ArrayBuilder(..)")
+  }
+}
+
+impl<'t, T, const N: usize> Drop for ArrayBuilder<'t, T, N> {
+  #[cold]
+  fn drop(&mut self) {
+    #[cfg(feature = "std")]
+    if mem::needs_drop::<T>() && self.remaining != N && !std::thread::panicking() {
+      panic!(
+        "memory leak: cannot drop `{Self}` containing values
+    if leaking is desired, use `ManuallyDrop<{Self}>` or `mem::forget`",
+        Self = core::any::type_name::<Self>()
+      )
+    }
+  }
+}
+
+crate::compile_fail_test!(array_builder_out_of_bounds: "E0080" {
+  let mut builder = loaned::ArrayBuilder::<u32, 2>::new();
+  let _ = builder.place_at::<2>(loaned::LoanedMut::new(0));
+});
@@ -0,0 +1,141 @@
+//! Keyed storage for loaned values, in the style of the [`slab`] crate.
+//!
+//! [`slab`]: https://docs.rs/slab/latest/slab/
+
+use crate::*;
+use core::fmt::Debug;
+
+/// A key identifying a value in a [`LoanSlab`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key(usize);
+
+impl Key {
+  /// Returns the index of the slot this key refers to, which is also its
+  /// position in the `Vec` returned by [`LoanSlab::into_inner`].
+  #[inline]
+  pub fn index(self) -> usize {
+    self.0
+  }
+}
+
+/// A slab of loaned values, which can be accessed by [`Key`].
+///
+/// Values are held as [`Loaned`], so that they can be borrowed through the
+/// slab while remaining loaned; a `LoanedMut` can be inserted after
+/// [downgrading](LoanedMut::downgrade) it. Removing a value returns its loan,
+/// so the value itself can't be dropped until `'t` has expired; its slot may be
+/// reused by a later insertion.
+///
+/// # Example
+/// ```
+/// use loaned::{slot::LoanSlab, take};
+///
+/// let mut slab = LoanSlab::new();
+/// let (a, a_ref) = slab.loan(Box::new(1));
+/// let (b, _) = slab.loan(Box::new(2));
+/// assert_eq!(slab.get(a), Some(&1));
+/// let removed = slab.remove(b).unwrap();
+/// let (c, _) = slab.loan(Box::new(3));
+/// assert_eq!(c, b);
+/// assert_eq!(*a_ref, 1);
+/// let values = take!(slab.into_inner());
+/// assert_eq!(values, [Some(Box::new(1)), Some(Box::new(3))]);
+/// assert_eq!(take!(removed), Box::new(2));
+/// ```
+pub struct LoanSlab<'t, T> {
+  entries: Vec<Option<Loaned<'t, T>>>,
+  free: Vec<usize>,
+}
+
+impl<'t, T> LoanSlab<'t, T> {
+  /// Constructs a new, empty `LoanSlab`.
+  #[inline]
+  pub const fn new() -> Self {
+    LoanSlab {
+      entries: Vec::new(),
+      free: Vec::new(),
+    }
+  }
+
+  /// Loans a value and inserts it into the slab, returning its key along with
+  /// the borrow.
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan(&mut self, value: T) -> (Key, &'t T::Target)
+  where
+    T: Loanable<'t>,
+  {
+    let (borrow, loaned) = Loaned::loan(value);
+    (self.insert(loaned), borrow)
+  }
+
+  /// Inserts a loaned value into the slab, returning its key.
+  pub fn insert(&mut self, loaned: Loaned<'t, T>) -> Key {
+    match self.free.pop() {
+      Some(index) => {
+        self.entries[index] = Some(loaned);
+        Key(index)
+      }
+      None => {
+        self.entries.push(Some(loaned));
+        Key(self.entries.len() - 1)
+      }
+    }
+  }
+
+  /// Borrows the value with the given key, if it is present.
+  #[inline]
+  pub fn get(&self, key: Key) -> Option<&'t T::Target>
+  where
+    T: Loanable<'t>,
+  {
+    Some(self.entries.get(key.0)?.as_ref()?.borrow())
+  }
+
+  /// Returns `true` if the slab contains a value with the given key.
+  #[inline]
+  pub fn contains(&self, key: Key) -> bool {
+    matches!(self.entries.get(key.0), Some(Some(_)))
+  }
+
+  /// Removes the value with the given key from the slab, returning its loan.
+  pub fn remove(&mut self, key: Key) -> Option<Loaned<'t, T>> {
+    let loaned = self.entries.get_mut(key.0)?.take()?;
+    self.free.push(key.0);
+    Some(loaned)
+  }
+
+  /// Returns the number of values in the slab.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.entries.len() - self.free.len()
+  }
+
+  /// Returns `true` if the slab contains no values.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Converts the slab into a loaned `Vec` of its slots, indexed by
+  /// [`Key::index`].
+  pub fn into_inner(self) -> Loaned<'t, Vec<Option<T>>> {
+    self
+      .entries
+      .into_iter()
+      .map(Loaned::from)
+      .collect::<Vec<_>>()
+      .into()
+  }
+}
+
+impl<'t, T> Default for LoanSlab<'t, T> {
+  fn default() -> Self {
+    LoanSlab::new()
+  }
+}
+
+impl<'t, T> Debug for LoanSlab<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "LoanSlab(..)")
+  }
+}
@@ -0,0 +1,103 @@
+//! Global counters of loans, for memory profiling. Requires the `stats`
+//! feature.
+//!
+//! The counters are cumulative: a single [`take!`](crate::take) can end any number of loans
+//! (e.g. of the nodes of a tree), so the number of loans still live can't be
+//! tracked precisely. Instead, comparing the number of loans and takes made
+//! by a piece of code can reveal loaned values that are never taken (e.g.
+//! because they were leaked with [`leak!`](crate::leak) or `ManuallyDrop`).
+
+#[cfg(feature = "stats")]
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+#[cfg(feature = "stats")]
+static LOANS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static LOANED_BYTES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static TAKES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static LEAKS: AtomicUsize = AtomicUsize::new(0);
+
+/// A snapshot of the loan counters, returned by [`snapshot`].
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct LoanStats {
+  /// The number of borrows loaned out (e.g. by [`LoanedMut::loan`], or for
+  /// each sub-loan of [`LoanedMut::loan_with`]).
+  ///
+  /// [`LoanedMut::loan`]: crate::LoanedMut::loan
+  /// [`LoanedMut::loan_with`]: crate::LoanedMut::loan_with
+  pub loans: usize,
+  /// The total size of the values borrowed by those loans.
+  pub loaned_bytes: usize,
+  /// The number of loaned values taken or dropped with [`take!`](crate::take), [`drop!`](crate::drop),
+  /// or the unchecked and fallible equivalents.
+  pub takes: usize,
+  /// The number of loaned values dropped without being taken, triggering
+  /// their [`DropPolicy`](crate::policy::DropPolicy).
+  pub leaks: usize,
+}
+
+#[cfg(feature = "stats")]
+impl LoanStats {
+  /// Returns the counts since the `earlier` snapshot.
+  pub fn since(self, earlier: LoanStats) -> LoanStats {
+    LoanStats {
+      loans: self.loans.wrapping_sub(earlier.loans),
+      loaned_bytes: self.loaned_bytes.wrapping_sub(earlier.loaned_bytes),
+      takes: self.takes.wrapping_sub(earlier.takes),
+      leaks: self.leaks.wrapping_sub(earlier.leaks),
+    }
+  }
+}
+
+/// Returns the current values of the loan counters, which are shared by all
+/// threads.
+///
+/// # Example
+/// ```
+/// use loaned::{stats, take, LoanedMut};
+/// let before = stats::snapshot();
+/// let (a, loaned_a) = LoanedMut::loan(Box::new(1u32));
+/// let (b, loaned_b) = LoanedMut::loan(Box::new(2u64));
+/// *a += 1;
+/// *b += 1;
+/// let ab = LoanedMut::<(Box<_>, Box<_>)>::from((loaned_a, loaned_b));
+/// take!(ab);
+/// let diff = stats::snapshot().since(before);
+/// // Other threads may also be loaning values.
+/// assert!(diff.loans >= 2);
+/// assert!(diff.loaned_bytes >= 12);
+/// assert!(diff.takes >= 1);
+/// ```
+#[cfg(feature = "stats")]
+pub fn snapshot() -> LoanStats {
+  LoanStats {
+    loans: LOANS.load(Relaxed),
+    loaned_bytes: LOANED_BYTES.load(Relaxed),
+    takes: TAKES.load(Relaxed),
+    leaks: LEAKS.load(Relaxed),
+  }
+}
+
+#[inline(always)]
+pub(crate) fn count_loan(_bytes: usize) {
+  #[cfg(feature = "stats")]
+  {
+    LOANS.fetch_add(1, Relaxed);
+    LOANED_BYTES.fetch_add(_bytes, Relaxed);
+  }
+}
+
+#[inline(always)]
+pub(crate) fn count_take() {
+  #[cfg(feature = "stats")]
+  TAKES.fetch_add(1, Relaxed);
+}
+
+#[inline(always)]
+pub(crate) fn count_leak() {
+  #[cfg(feature = "stats")]
+  LEAKS.fetch_add(1, Relaxed);
+}
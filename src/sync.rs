@@ -0,0 +1,55 @@
+//! Loans of synchronized values, which can be mutated through locks both by
+//! the holders of the loaned borrows and by the owner of the loan.
+//!
+//! Atomics allow interior mutation of a [`Loaned`] value without any extra
+//! machinery; for structured data, wrap it in a [`Mutex`] or [`RwLock`]
+//! instead of giving each field its own `Cell` or atomic.
+//!
+//! # Example
+//! ```
+//! use loaned::{sync::LoanedMutex, take};
+//! let (borrow, loaned) = LoanedMutex::loan_mutex(vec![1]);
+//! std::thread::scope(|s| {
+//!   s.spawn(|| borrow.lock().unwrap().push(2));
+//! });
+//! loaned.lock().unwrap().push(3);
+//! assert_eq!(take!(loaned).into_inner().unwrap(), [1, 2, 3]);
+//! ```
+
+use crate::*;
+use std::sync::{Mutex, RwLock};
+
+/// A [`Mutex`], immutably loaned for `'t`.
+pub type LoanedMutex<'t, T> = Loaned<'t, Box<Mutex<T>>>;
+
+/// An [`RwLock`], immutably loaned for `'t`.
+pub type LoanedRwLock<'t, T> = Loaned<'t, Box<RwLock<T>>>;
+
+impl<'t, T: 't> LoanedMutex<'t, T> {
+  /// Wraps `value` in a [`Mutex`] and loans it, returning the borrow along
+  /// with the loaned `Mutex`.
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_mutex(value: T) -> (&'t Mutex<T>, Self) {
+    Loaned::loan(Box::new(Mutex::new(value)))
+  }
+}
+
+impl<'t, T: 't> LoanedRwLock<'t, T> {
+  /// Wraps `value` in an [`RwLock`] and loans it, returning the borrow along
+  /// with the loaned `RwLock`.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{sync::LoanedRwLock, take};
+  /// let (borrow, loaned) = LoanedRwLock::loan_rwlock(String::from("a"));
+  /// borrow.write().unwrap().push('b');
+  /// assert_eq!(*loaned.read().unwrap(), "ab");
+  /// assert_eq!(take!(loaned).into_inner().unwrap(), "ab");
+  /// ```
+  #[inline]
+  #[cfg_attr(feature = "debug-loans", track_caller)]
+  pub fn loan_rwlock(value: T) -> (&'t RwLock<T>, Self) {
+    Loaned::loan(Box::new(RwLock::new(value)))
+  }
+}
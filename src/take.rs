@@ -13,8 +13,11 @@ use crate::*;
 #[macro_export]
 macro_rules! take {
   ($loaned:expr) => {{
+    let loaned_value = $loaned;
     let mut loaned = ();
-    unsafe { $crate::__take($loaned, &mut loaned) }
+    let value = unsafe { $crate::__take(loaned_value, &mut loaned) };
+    $crate::__release_origins!(&value);
+    value
   }};
 }
 
@@ -32,20 +35,126 @@ macro_rules! take {
 #[macro_export]
 macro_rules! drop {
   ($loaned:expr) => {{
+    let loaned_input = $loaned;
     let loaned_value;
     let mut loaned = ();
-    loaned_value = unsafe { $crate::__take($loaned, &mut loaned) };
+    loaned_value = unsafe { $crate::__take(loaned_input, &mut loaned) };
+    $crate::__release_origins!(&loaned_value);
     let _ = loaned_value;
   }};
 }
 
+/// Drops each loan yielded by an iterator (e.g. a `Vec` of [`LoanedMut`]s),
+/// statically ensuring that `'t` is expired.
+///
+/// Unlike [`drop!`], this doesn't require the loans to be converted into a
+/// single loaned collection first. The items may be any [`LoanWrapper`].
+///
+/// If dropping one of the values panics, the remaining values are still
+/// dropped.
+///
+/// # Example
+/// ```
+/// use loaned::{drop_all, LoanedMut};
+/// let (a, loaned) = LoanedMut::loan(Box::new(1));
+/// let mut loans = vec![loaned, LoanedMut::new(Box::new(2))];
+/// loans.push(LoanedMut::new(Box::new(3)));
+/// *a = 0;
+/// drop_all!(loans); // drops all three boxes
+/// ```
+#[macro_export]
+macro_rules! drop_all {
+  ($loans:expr) => {{
+    let loaned_input = $loans;
+    let mut loaned = ();
+    unsafe {
+      $crate::__drop_all(loaned_input, &mut loaned, |value| {
+        $crate::__release_origins!(value)
+      })
+    }
+  }};
+}
+
+/// Wraps a [`Loaned`] or [`LoanedMut`] in a [`ManuallyDrop`](core::mem::ManuallyDrop),
+/// intentionally leaking the inner value unless it is later unwrapped and
+/// taken.
+///
+/// # Example
+/// ```
+/// use loaned::{leak, take, LoanedMut};
+/// use std::mem::ManuallyDrop;
+/// let (borrow, loaned) = LoanedMut::loan(Box::new(123));
+/// let leaked = leak!(loaned); // dropping `leaked` would leak the box
+/// *borrow = 456;
+/// assert_eq!(take!(ManuallyDrop::into_inner(leaked)), Box::new(456));
+/// ```
+#[macro_export]
+macro_rules! leak {
+  ($loaned:expr) => {
+    ::core::mem::ManuallyDrop::new($loaned)
+  };
+}
+
 #[doc(hidden)]
+#[inline(always)]
 pub unsafe fn __take<'t, T: 't, L: Placeable<'t, T>>(loaned: L, _: &'t mut ()) -> T {
   let mut place = MaybeUninit::uninit();
   loaned.place(unsafe { &mut *(&mut place as *mut _) });
+  stats::count_take();
   place.assume_init()
 }
 
+#[doc(hidden)]
+pub unsafe fn __drop_all<'t, L: LoanWrapper<'t>, F: FnMut(&L::Value)>(
+  loans: impl IntoIterator<Item = L>,
+  _: &'t mut (),
+  release: F,
+) {
+  // Drops the remaining loans if dropping one of the values panics.
+  struct DropRest<'t, L: LoanWrapper<'t>, I: Iterator<Item = L>, F: FnMut(&L::Value)>(
+    I,
+    F,
+    PhantomData<&'t ()>,
+  );
+  impl<'t, L: LoanWrapper<'t>, I: Iterator<Item = L>, F: FnMut(&L::Value)> DropRest<'t, L, I, F> {
+    unsafe fn drop_rest(&mut self) {
+      for loaned in &mut self.0 {
+        let value = loaned.into_loan().take_unchecked();
+        (self.1)(&value);
+      }
+    }
+  }
+  impl<'t, L: LoanWrapper<'t>, I: Iterator<Item = L>, F: FnMut(&L::Value)> Drop
+    for DropRest<'t, L, I, F>
+  {
+    fn drop(&mut self) {
+      unsafe { self.drop_rest() }
+    }
+  }
+  DropRest(loans.into_iter(), release, PhantomData).drop_rest()
+}
+
+// The argument of `take!` must not be evaluated within its `unsafe` block.
+crate::compile_fail_test!(take_unsafe_argument: "E0133" {
+  unsafe fn make<'t>() -> loaned::LoanedMut<'t, Box<u32>> {
+    loaned::LoanedMut::new(Box::new(0))
+  }
+  let _ = loaned::take!(make());
+});
+
+crate::compile_fail_test!(drop_unsafe_argument: "E0133" {
+  unsafe fn make<'t>() -> loaned::LoanedMut<'t, Box<u32>> {
+    loaned::LoanedMut::new(Box::new(0))
+  }
+  loaned::drop!(make());
+});
+
+crate::compile_fail_test!(use_after_drop_all: "E0716" {
+  let (borrow, loaned) = loaned::LoanedMut::loan(Box::new(0));
+  loaned::drop_all!([loaned]);
+  *borrow = 1;
+});
+
 mod test_drop_cyclic {
   /**
   ```rust
@@ -0,0 +1,101 @@
+//! Macros for writing compile-fail tests for loan-based APIs.
+//!
+//! The soundness of an API built on loans usually depends on code *not*
+//! compiling: the pointee of a [`LoanedMut`](crate::LoanedMut) must not be
+//! accessible, and a loan must not be taken while its borrows are still in use.
+//! These macros generate doctests (marked `compile_fail`) asserting this; they
+//! are run with `cargo test --doc`, like the rest of a crate's doctests.
+//!
+//! Each test must be self-contained, as doctests are compiled as separate
+//! crates, so the setup code needs to import whatever it uses.
+//!
+//! # Example
+//! ```
+//! use loaned::testutil::{assert_no_mut_access, assert_no_take_while_borrowed};
+//!
+//! assert_no_mut_access!(loaned_mut_has_no_deref_mut {
+//!   let (_, loaned) = loaned::LoanedMut::loan(Box::new(1));
+//! } loaned);
+//!
+//! assert_no_take_while_borrowed!(take_while_borrowed {
+//!   let (borrow, loaned) = loaned::LoanedMut::loan(Box::new(1));
+//! } loaned, *borrow = 2);
+//! ```
+
+#[doc(inline)]
+pub use crate::{
+  assert_no_access, assert_no_mut_access, assert_no_take_while_borrowed, compile_fail_test,
+};
+
+/// Generates a doctest asserting that the given code fails to compile,
+/// optionally with a specific error code.
+///
+/// # Example
+/// ```
+/// loaned::compile_fail_test!(no_deref: "E0614" {
+///   let loaned = loaned::LoanedMut::new(Box::new(1));
+///   let _ = **loaned;
+/// });
+/// ```
+#[macro_export]
+macro_rules! compile_fail_test {
+  ($name:ident $(: $code:literal)? { $($body:tt)* }) => {
+    #[doc = concat!("```compile_fail", $(",", $code,)? "\n", stringify!($($body)*), "\n```")]
+    #[allow(non_snake_case, dead_code)]
+    mod $name {}
+  };
+}
+
+/// Generates a doctest asserting that the value of an expression (after the
+/// setup code) does not implement `Deref`, i.e. that its pointee can't be
+/// accessed.
+#[macro_export]
+macro_rules! assert_no_access {
+  ($name:ident { $($setup:tt)* } $value:expr) => {
+    $crate::compile_fail_test!($name: "E0277" {
+      $($setup)*
+      fn assert_access<T: ::core::ops::Deref + ?Sized>(_: &T) {}
+      assert_access(&$value);
+    });
+  };
+}
+
+/// Generates a doctest asserting that the value of an expression (after the
+/// setup code) does not implement `DerefMut`, i.e. that it can't be accessed
+/// mutably.
+#[macro_export]
+macro_rules! assert_no_mut_access {
+  ($name:ident { $($setup:tt)* } $value:expr) => {
+    $crate::compile_fail_test!($name: "E0277" {
+      $($setup)*
+      fn assert_mut_access<T: ::core::ops::DerefMut + ?Sized>(_: &T) {}
+      assert_mut_access(&$value);
+    });
+  };
+}
+
+/// Generates a doctest asserting that a loan can't be taken (with
+/// [`take!`](crate::take!)) while the given use of its borrow comes after it.
+#[macro_export]
+macro_rules! assert_no_take_while_borrowed {
+  ($name:ident { $($setup:tt)* } $loaned:expr, $use:expr) => {
+    $crate::compile_fail_test!($name: "E0716" {
+      $($setup)*
+      let value = loaned::take!($loaned);
+      $use;
+      let _ = value;
+    });
+  };
+}
+
+assert_no_access!(loaned_mut_no_access {
+  let loaned = loaned::LoanedMut::new(Box::new(1));
+} loaned);
+
+assert_no_mut_access!(loaned_no_mut_access {
+  let loaned = loaned::Loaned::new(Box::new(1));
+} loaned);
+
+assert_no_take_while_borrowed!(take_while_borrowed {
+  let (borrow, loaned) = loaned::LoanedMut::loan(Box::new(1));
+} loaned, *borrow = 2);
@@ -0,0 +1,69 @@
+use crate::*;
+
+/// The hole left by [`LoanedMut::loan_uninit`], which must be written to
+/// before the loaned `Box` can be [initialized](LoanedMut::init).
+pub struct UninitHole<'t, T>(&'t mut MaybeUninit<T>);
+
+/// Proof that an [`UninitHole`] has been written to, consumed by
+/// [`LoanedMut::init`].
+pub struct Initialized<'t, T> {
+  ptr: *const T,
+  _borrow: PhantomData<&'t mut T>,
+}
+
+impl<'t, T> UninitHole<'t, T> {
+  /// Initializes the hole with `value`, returning a borrow of it along with
+  /// proof of its initialization.
+  pub fn write(self, value: T) -> (&'t mut T, Initialized<'t, T>) {
+    let borrow = self.0.write(value);
+    let proof = Initialized {
+      ptr: borrow as *const T,
+      _borrow: PhantomData,
+    };
+    (borrow, proof)
+  }
+}
+
+impl<'t, T> LoanedMut<'t, Box<MaybeUninit<T>>> {
+  /// Loans an uninitialized allocation, returning a hole to be written to
+  /// later. Once it has been, the proof of initialization can be passed to
+  /// [`LoanedMut::init`] to get a `LoanedMut<'t, Box<T>>`.
+  ///
+  /// # Panics
+  /// Panics if `T` is zero-sized, as the proof of initialization could not
+  /// distinguish between allocations.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut};
+  /// let (hole, loaned) = LoanedMut::loan_uninit(Box::new_uninit());
+  /// let (borrow, proof) = hole.write(1);
+  /// let loaned = loaned.init(proof);
+  /// *borrow += 1;
+  /// assert_eq!(take!(loaned), Box::new(2));
+  /// ```
+  pub fn loan_uninit(value: Box<MaybeUninit<T>>) -> (UninitHole<'t, T>, Self) {
+    assert!(
+      mem::size_of::<T>() != 0,
+      "cannot loan an uninitialized zero-sized value"
+    );
+    let (borrow, loaned) = LoanedMut::loan(value);
+    (UninitHole(borrow), loaned)
+  }
+
+  /// Converts the loaned allocation into a `LoanedMut<'t, Box<T>>`, given
+  /// proof that its hole has been written to.
+  ///
+  /// # Panics
+  /// Panics if `proof` was produced by the hole of a different allocation.
+  pub fn init(self, proof: Initialized<'t, T>) -> LoanedMut<'t, Box<T>> {
+    let raw = ManuallyDrop::new(self.into_raw());
+    let ptr =
+      unsafe { ptr::read((&*raw as *const RawLoaned<Box<MaybeUninit<T>>>).cast::<*const T>()) };
+    assert!(
+      ptr == proof.ptr,
+      "proof of initialization is for a different allocation"
+    );
+    unsafe { LoanedMut::from_raw(mem::transmute_copy(&*raw)) }
+  }
+}
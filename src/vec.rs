@@ -0,0 +1,277 @@
+use crate::*;
+use core::fmt::Debug;
+
+/// A `Vec` of mutably loaned values, which can be converted into a
+/// [`LoanedMut<'t, Vec<T>>`](LoanedMut) without reallocating.
+///
+/// The pointees of the loaned values cannot be accessed through a `LoanedVec`;
+/// reading them would invalidate the borrows that were loaned out.
+///
+/// Loans can be added in batches with `Extend` as they are produced, so they
+/// don't need to be collected before converting. Once converted, the `Vec`'s
+/// buffer may itself be loaned, so it can't be extended further; to stream
+/// loans into an existing `Vec` instead, use [`VecPlace`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, LoanedMut, LoanedVec};
+/// let mut vec = LoanedVec::with_capacity(2);
+/// let (a, loaned) = LoanedMut::loan(Box::new(0));
+/// vec.push(loaned);
+/// vec.push(LoanedMut::new(Box::new(2)));
+/// let vec = LoanedMut::<Vec<_>>::from(vec);
+/// *a = 1;
+/// assert_eq!(take!(vec), [Box::new(1), Box::new(2)]);
+/// ```
+pub struct LoanedVec<'t, T>(Vec<LoanedMut<'t, T>>);
+
+impl<'t, T> LoanedVec<'t, T> {
+  /// Constructs a new, empty `LoanedVec`.
+  #[inline]
+  pub const fn new() -> Self {
+    LoanedVec(Vec::new())
+  }
+
+  /// Constructs a new, empty `LoanedVec` with at least the specified capacity.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    LoanedVec(Vec::with_capacity(capacity))
+  }
+
+  /// Appends a loaned value to the end of the `LoanedVec`.
+  #[inline]
+  pub fn push(&mut self, loaned: LoanedMut<'t, T>) {
+    self.0.push(loaned)
+  }
+
+  /// Moves all of the loaned values of `other` to the end of `self`, leaving
+  /// `other` empty.
+  #[inline]
+  pub fn append(&mut self, other: &mut Self) {
+    self.0.append(&mut other.0)
+  }
+
+  /// Reserves capacity for at least `additional` more elements.
+  #[inline]
+  pub fn reserve(&mut self, additional: usize) {
+    self.0.reserve(additional)
+  }
+
+  /// Returns the number of elements in the `LoanedVec`.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if the `LoanedVec` has no elements.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Returns the number of elements the `LoanedVec` can hold without
+  /// reallocating.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.0.capacity()
+  }
+
+  /// Swaps two loaned values. The values themselves (e.g. `Box`es) are moved,
+  /// but their pointees are not, so the loaned borrows remain valid.
+  ///
+  /// # Panics
+  /// Panics if `a` or `b` are out of bounds.
+  #[inline]
+  pub fn swap(&mut self, a: usize, b: usize) {
+    self.0.swap(a, b)
+  }
+
+  /// Sorts the loaned values by a key, preserving the order of equal values.
+  ///
+  /// As the values cannot be accessed, the key is computed from each value's
+  /// current index; `f` is called exactly once for each index, in order.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut, LoanedVec};
+  /// let names = ["c", "a", "b"];
+  /// let mut vec = LoanedVec::new();
+  /// let (a, loaned) = LoanedMut::loan(Box::new(0));
+  /// vec.push(loaned);
+  /// vec.push(LoanedMut::new(Box::new(1)));
+  /// vec.push(LoanedMut::new(Box::new(2)));
+  /// vec.sort_by_cached_key(|i| names[i]);
+  /// *a = 3;
+  /// let vec = LoanedMut::<Vec<_>>::from(vec);
+  /// assert_eq!(take!(vec), [Box::new(1), Box::new(2), Box::new(3)]);
+  /// ```
+  pub fn sort_by_cached_key<K: Ord>(&mut self, f: impl FnMut(usize) -> K) {
+    let keys = (0..self.len()).map(f).collect::<Vec<_>>();
+    let mut keyed = keys.into_iter().zip(self.0.drain(..)).collect::<Vec<_>>();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+    self.0.extend(keyed.into_iter().map(|(_, loaned)| loaned));
+  }
+
+  /// Retains only the loaned values for which `f` returns `true`, given each
+  /// value's current index. The removed values are returned, as they are still
+  /// loaned and cannot be dropped.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut, LoanedVec};
+  /// let mut vec = (0..5).map(|i| LoanedMut::new(Box::new(i))).collect::<LoanedVec<_>>();
+  /// let removed = vec.retain(|i| i % 2 == 0);
+  /// assert_eq!(take!(LoanedMut::<Vec<_>>::from(vec)), [0, 2, 4].map(Box::new));
+  /// assert_eq!(take!(LoanedMut::<Vec<_>>::from(removed)), [1, 3].map(Box::new));
+  /// ```
+  pub fn retain(&mut self, f: impl FnMut(usize) -> bool) -> LoanedVec<'t, T> {
+    let keep = (0..self.len()).map(f).collect::<Vec<_>>();
+    let mut removed = LoanedVec::new();
+    let mut kept = Vec::with_capacity(self.len());
+    for (keep, loaned) in keep.into_iter().zip(self.0.drain(..)) {
+      if keep {
+        kept.push(loaned);
+      } else {
+        removed.push(loaned);
+      }
+    }
+    self.0.extend(kept);
+    removed
+  }
+
+  /// Removes consecutive loaned values which have the same key, keeping the
+  /// first of each run. The key is computed from each value's current index,
+  /// as with [`LoanedVec::sort_by_cached_key`]. The removed values are
+  /// returned, as they are still loaned and cannot be dropped.
+  ///
+  /// # Example
+  /// ```
+  /// use loaned::{take, LoanedMut, LoanedVec};
+  /// let keys = [1, 1, 2, 3, 3, 3];
+  /// let mut vec = (0..6).map(|i| LoanedMut::new(Box::new(i))).collect::<LoanedVec<_>>();
+  /// let removed = vec.dedup_by_key(|i| keys[i]);
+  /// assert_eq!(take!(LoanedMut::<Vec<_>>::from(vec)), [0, 2, 3].map(Box::new));
+  /// assert_eq!(take!(LoanedMut::<Vec<_>>::from(removed)), [1, 4, 5].map(Box::new));
+  /// ```
+  pub fn dedup_by_key<K: PartialEq>(&mut self, f: impl FnMut(usize) -> K) -> LoanedVec<'t, T> {
+    let keys = (0..self.len()).map(f).collect::<Vec<_>>();
+    let mut last = None;
+    let keep = keys
+      .into_iter()
+      .map(|key| {
+        let keep = last.as_ref() != Some(&key);
+        if keep {
+          last = Some(key);
+        }
+        keep
+      })
+      .collect::<Vec<_>>();
+    let mut keep = keep.into_iter();
+    self.retain(|_| keep.next().unwrap())
+  }
+}
+
+impl<'t, T> Default for LoanedVec<'t, T> {
+  fn default() -> Self {
+    LoanedVec::new()
+  }
+}
+
+impl<'t, T> Debug for LoanedVec<'t, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "LoanedVec(..)")
+  }
+}
+
+impl<'t, T> Extend<LoanedMut<'t, T>> for LoanedVec<'t, T> {
+  fn extend<I: IntoIterator<Item = LoanedMut<'t, T>>>(&mut self, iter: I) {
+    self.0.extend(iter)
+  }
+}
+
+impl<'t, T> Extend<Loaned<'t, T>> for LoanedVec<'t, T> {
+  fn extend<I: IntoIterator<Item = Loaned<'t, T>>>(&mut self, iter: I) {
+    self.0.extend(iter.into_iter().map(LoanedMut::from))
+  }
+}
+
+impl<'t, T> FromIterator<LoanedMut<'t, T>> for LoanedVec<'t, T> {
+  fn from_iter<I: IntoIterator<Item = LoanedMut<'t, T>>>(iter: I) -> Self {
+    LoanedVec(iter.into_iter().collect())
+  }
+}
+
+impl<'t, T> From<Vec<LoanedMut<'t, T>>> for LoanedVec<'t, T> {
+  fn from(value: Vec<LoanedMut<'t, T>>) -> Self {
+    LoanedVec(value)
+  }
+}
+
+impl<'t, T> From<LoanedVec<'t, T>> for LoanedMut<'t, Vec<T>> {
+  #[inline]
+  fn from(value: LoanedVec<'t, T>) -> Self {
+    value.0.into()
+  }
+}
+
+/// An iterator adapter over loans, which yields a borrow of each loaned
+/// value and collects the loans themselves into a [`LoanedVec`].
+///
+/// # Example
+/// ```
+/// use loaned::{take, Loaned, LoanedIter};
+/// let mut iter = LoanedIter::new((1..=3).map(|i| Loaned::new(Box::new(i))));
+/// let mut borrows = Vec::new();
+/// for borrow in &mut iter {
+///   borrows.push(borrow);
+/// }
+/// assert_eq!(borrows, [&1, &2, &3]);
+/// assert_eq!(take!(iter.into_inner()), [1, 2, 3].map(Box::new));
+/// ```
+pub struct LoanedIter<'t, I: Iterator<Item = Loaned<'t, T>>, T> {
+  iter: I,
+  loans: LoanedVec<'t, T>,
+}
+
+impl<'t, I: Iterator<Item = Loaned<'t, T>>, T> LoanedIter<'t, I, T> {
+  /// Wraps an iterator over loans.
+  #[inline]
+  pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+    LoanedIter {
+      iter: iter.into_iter(),
+      loans: LoanedVec::new(),
+    }
+  }
+
+  /// Returns the loans collected so far, along with any which have not yet
+  /// been yielded.
+  pub fn into_inner(mut self) -> LoanedMut<'t, Vec<T>> {
+    self.loans.extend(self.iter.map(LoanedMut::from));
+    self.loans.into()
+  }
+}
+
+impl<'t, I: Iterator<Item = Loaned<'t, T>>, T: Loanable<'t>> Iterator for LoanedIter<'t, I, T>
+where
+  T::Target: 't,
+{
+  type Item = &'t T::Target;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let loaned = self.iter.next()?;
+    let borrow = loaned.borrow();
+    self.loans.push(loaned.into());
+    Some(borrow)
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'t, I: Iterator<Item = Loaned<'t, T>>, T> Debug for LoanedIter<'t, I, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "LoanedIter(..)")
+  }
+}
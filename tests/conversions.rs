@@ -0,0 +1,223 @@
+//! Property tests for the `unsafe` conversion layer: the container
+//! conversions, `Place` impls, and `RawLoaned` round-trips, over randomized
+//! structures (including zero-sized and highly-aligned types).
+//!
+//! These are also meant to be run under Miri, with fewer cases:
+//! `PROPTEST_CASES=16 cargo miri test --test conversions`.
+
+#![cfg(feature = "std")]
+
+use loaned::{raw::RawLoaned, take, Loaned, LoanedMut, VecPlace};
+use proptest::prelude::*;
+use std::{cell::Cell, mem::MaybeUninit};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(align(64))]
+struct Aligned(u8);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Zst;
+
+fn aligned() -> impl Strategy<Value = Aligned> {
+  any::<u8>().prop_map(Aligned)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PlaceKind {
+  MaybeUninit,
+  Option,
+  Value,
+  Box,
+  Cell,
+  Vec,
+}
+
+fn place_kind() -> impl Strategy<Value = PlaceKind> {
+  prop_oneof![
+    Just(PlaceKind::MaybeUninit),
+    Just(PlaceKind::Option),
+    Just(PlaceKind::Value),
+    Just(PlaceKind::Box),
+    Just(PlaceKind::Cell),
+    Just(PlaceKind::Vec),
+  ]
+}
+
+proptest! {
+  #[test]
+  fn vec_round_trip(values in prop::collection::vec(any::<u32>(), 0..64), extra in 0usize..16) {
+    let mut loans = Vec::with_capacity(values.len() + extra);
+    let mut borrows = Vec::new();
+    for &value in &values {
+      let (borrow, loaned) = LoanedMut::loan(Box::new(value));
+      borrows.push(borrow);
+      loans.push(loaned);
+    }
+    let capacity = loans.capacity();
+    let loaned = LoanedMut::<Vec<Box<_>>>::from(loans);
+    for borrow in borrows {
+      *borrow = borrow.wrapping_mul(3);
+    }
+    let vec = take!(loaned);
+    prop_assert_eq!(vec.capacity(), capacity);
+    let expected = values.iter().map(|x| Box::new(x.wrapping_mul(3))).collect::<Vec<_>>();
+    prop_assert_eq!(vec, expected);
+  }
+
+  #[test]
+  fn shared_vec_round_trip(values in prop::collection::vec(aligned(), 0..32)) {
+    let (borrows, loans): (Vec<&Aligned>, Vec<_>) =
+      values.iter().map(|&value| Loaned::loan(Box::new(value))).unzip();
+    let loaned = Loaned::<Vec<Box<_>>>::from(loans);
+    for (borrow, value) in borrows.iter().zip(&values) {
+      prop_assert_eq!(*borrow, value);
+      prop_assert_eq!(*borrow as *const Aligned as usize % 64, 0);
+    }
+    let vec = take!(loaned);
+    prop_assert_eq!(vec.into_iter().map(|x| *x).collect::<Vec<_>>(), values);
+  }
+
+  #[test]
+  fn zst_vec(len in 0usize..1024) {
+    let loans = (0..len).map(|_| LoanedMut::new(Zst)).collect::<Vec<_>>();
+    let capacity = loans.capacity();
+    let loaned = LoanedMut::<Vec<Zst>>::from(loans);
+    let vec = take!(loaned);
+    prop_assert_eq!(vec.len(), len);
+    prop_assert_eq!(vec.capacity(), capacity);
+  }
+
+  #[test]
+  fn aligned_values(values in prop::collection::vec(aligned(), 0..32)) {
+    let loans = values.iter().map(|&value| LoanedMut::new(value)).collect::<Vec<_>>();
+    let loaned = LoanedMut::<Vec<Aligned>>::from(loans);
+    let vec = take!(loaned);
+    prop_assert_eq!(vec.as_ptr() as usize % 64, 0);
+    prop_assert_eq!(vec, values);
+  }
+
+  #[test]
+  fn nested_tuples(a in any::<u8>(), b in any::<u64>(), c in aligned(), d in any::<bool>()) {
+    let (borrow_a, loaned_a) = LoanedMut::loan(Box::new(a));
+    let (borrow_c, loaned_c) = LoanedMut::loan(Box::new(c));
+    let inner = LoanedMut::<(u64, Box<Aligned>, Zst)>::from((
+      LoanedMut::new(b),
+      loaned_c,
+      LoanedMut::new(Zst),
+    ));
+    let outer = LoanedMut::<(Box<u8>, (u64, Box<Aligned>, Zst), Option<Box<bool>>)>::from((
+      loaned_a,
+      inner,
+      LoanedMut::new(Some(Box::new(d))),
+    ));
+    let (loaned_a, inner, loaned_d): (LoanedMut<Box<u8>>, LoanedMut<_>, LoanedMut<_>) = outer.into();
+    let (loaned_b, loaned_c, loaned_zst): (LoanedMut<u64>, LoanedMut<Box<Aligned>>, LoanedMut<Zst>) =
+      inner.into();
+    let loaned_d = Option::<LoanedMut<Box<bool>>>::from(loaned_d).unwrap();
+    *borrow_a = a.wrapping_add(1);
+    borrow_c.0 = c.0.wrapping_add(1);
+    let merged = LoanedMut::<(Box<bool>, Zst, Box<Aligned>, u64, Box<u8>)>::from((
+      loaned_d, loaned_zst, loaned_c, loaned_b, loaned_a,
+    ));
+    let (d2, _, c2, b2, a2) = take!(merged);
+    prop_assert_eq!((*a2, b2, *c2, *d2), (a.wrapping_add(1), b, Aligned(c.0.wrapping_add(1)), d));
+  }
+
+  #[test]
+  fn array_round_trip(values in any::<[u16; 8]>()) {
+    let mut borrows = Vec::new();
+    let loans = values.map(|value| {
+      let (borrow, loaned) = LoanedMut::loan(Box::new(value));
+      borrows.push(borrow);
+      loaned
+    });
+    let loaned = LoanedMut::from_array(loans);
+    let loans = loaned.into_array();
+    let loaned = LoanedMut::<[Box<u16>; 8]>::from(loans);
+    for borrow in borrows {
+      *borrow ^= 0xffff;
+    }
+    prop_assert_eq!(take!(loaned), values.map(|value| Box::new(value ^ 0xffff)));
+  }
+
+  #[test]
+  fn raw_round_trip(ops in prop::collection::vec((any::<bool>(), any::<u8>()), 0..64)) {
+    let mut slots: Vec<Option<RawLoaned<Box<u64>>>> = Vec::new();
+    let mut borrows = Vec::new();
+    for (i, (insert, index)) in ops.into_iter().enumerate() {
+      if insert || slots.is_empty() {
+        let (borrow, loaned) = LoanedMut::loan(Box::new(i as u64));
+        borrows.push((borrow, i as u64));
+        slots.push(Some(loaned.into_raw()));
+      } else {
+        // Move a loaned value between slots, through `LoanedMut`.
+        let from = index as usize % slots.len();
+        if let Some(raw) = slots[from].take() {
+          let loaned = unsafe { LoanedMut::<Box<u64>>::from_raw(raw) };
+          slots.push(Some(loaned.into_raw()));
+        }
+      }
+    }
+    for (borrow, value) in &mut borrows {
+      **borrow += 1000;
+      *value += 1000;
+    }
+    let loaned = slots
+      .into_iter()
+      .flatten()
+      .map(|raw| unsafe { LoanedMut::from_raw(raw) })
+      .collect::<LoanedMut<Vec<Box<u64>>>>();
+    let mut values = take!(loaned).into_iter().map(|x| *x).collect::<Vec<_>>();
+    values.sort();
+    let mut expected = borrows.into_iter().map(|(_, value)| value).collect::<Vec<_>>();
+    expected.sort();
+    prop_assert_eq!(values, expected);
+  }
+
+  #[test]
+  fn places(kind in place_kind(), value in any::<u32>(), old in any::<u32>()) {
+    let mut vec = vec![Box::new(old)];
+    let (borrow, loaned) = LoanedMut::loan(Box::new(value));
+    let result = match kind {
+      PlaceKind::MaybeUninit => {
+        let mut place = MaybeUninit::uninit();
+        loaned.place(&mut place);
+        *borrow = borrow.wrapping_add(1);
+        unsafe { place.assume_init() }
+      }
+      PlaceKind::Option => {
+        let mut place = Some(Box::new(old));
+        loaned.place(&mut place);
+        *borrow = borrow.wrapping_add(1);
+        place.unwrap()
+      }
+      PlaceKind::Value => {
+        let mut place = Box::new(old);
+        loaned.place(&mut place);
+        *borrow = borrow.wrapping_add(1);
+        place
+      }
+      PlaceKind::Box => {
+        let mut place = Box::new(Box::new(old));
+        let ptr = &*place as *const Box<u32>;
+        loaned.place(&mut place);
+        *borrow = borrow.wrapping_add(1);
+        prop_assert!(std::ptr::eq(&*place, ptr));
+        *place
+      }
+      PlaceKind::Cell => {
+        let mut place = Cell::new(None);
+        loaned.place(&mut place);
+        *borrow = borrow.wrapping_add(1);
+        place.into_inner().unwrap()
+      }
+      PlaceKind::Vec => {
+        VecPlace::new(&mut vec).push(loaned);
+        *borrow = borrow.wrapping_add(1);
+        prop_assert_eq!(&*vec[0], &old);
+        vec.pop().unwrap()
+      }
+    };
+    prop_assert_eq!(*result, value.wrapping_add(1));
+  }
+}